@@ -0,0 +1,51 @@
+//! Benchmarks day 8 on synthetic maps packed with antennas, far denser
+//! than the puzzle's:
+//!
+//!     cargo bench --bench antennas
+use advent_2024::days::day08::Day08;
+use advent_2024::Solver;
+use common::Lcg;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+mod common;
+
+/// A `size` by `size` map where one tile in `sparsity` holds an antenna,
+/// spread over all 62 frequencies.
+fn synthetic_input(size: usize, sparsity: usize) -> String {
+    let labels: Vec<char> = ('0'..='9').chain('a'..='z').chain('A'..='Z').collect();
+    let mut rng = Lcg(2024);
+    let mut input = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            input.push(if rng.below(sparsity) == 0 {
+                labels[rng.below(labels.len())]
+            } else {
+                '.'
+            });
+        }
+        input.push('\n');
+    }
+    input
+}
+
+fn antennas(c: &mut Criterion) {
+    for part in [1, 2] {
+        let mut group = c.benchmark_group(format!("antennas/part{}", part));
+        for sparsity in [50, 10] {
+            let input = synthetic_input(200, sparsity);
+            group.bench_with_input(
+                BenchmarkId::from_parameter(format!("1 in {}", sparsity)),
+                &input,
+                |b, input| b.iter(|| Day08.part(part, input)),
+            );
+        }
+        group.finish();
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = antennas
+}
+criterion_main!(benches);
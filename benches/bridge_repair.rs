@@ -0,0 +1,56 @@
+//! Benchmarks day 7's searches on synthetic equations with far more
+//! arguments than the puzzle's:
+//!
+//!     cargo bench --bench bridge_repair
+use advent_2024::days::day07::{PART1_VARIANTS, PART2_VARIANTS};
+use common::Lcg;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+mod common;
+
+/// `equations` equations of `len` small arguments each, combined by random
+/// operators.  Every other test value is off by one, so most of those have
+/// no solution and the whole search runs.
+fn synthetic_input(len: usize, equations: usize) -> String {
+    let mut rng = Lcg(2024);
+    let mut input = String::new();
+    while input.lines().count() < equations {
+        let args: Vec<u64> = (0..len).map(|_| rng.below(20) as u64 + 1).collect();
+        let value = args[1..]
+            .iter()
+            .try_fold(args[0], |acc, &arg| match rng.below(3) {
+                0 => acc.checked_add(arg),
+                1 => acc.checked_mul(arg),
+                _ => format!("{}{}", acc, arg).parse().ok(),
+            });
+        let Some(value) = value else {
+            continue;
+        };
+        let value = value + input.lines().count() as u64 % 2;
+        let args: Vec<String> = args.iter().map(u64::to_string).collect();
+        input += &format!("{}: {}\n", value, args.join(" "));
+    }
+    input
+}
+
+fn bridge_repair(c: &mut Criterion) {
+    let input = synthetic_input(14, 200);
+    for (part, variants) in [(1, PART1_VARIANTS), (2, PART2_VARIANTS)] {
+        let mut group = c.benchmark_group(format!("bridge_repair/part{}", part));
+        for variant in variants {
+            group.bench_with_input(
+                BenchmarkId::from_parameter(variant.name),
+                &input,
+                |b, input| b.iter(|| (variant.run)(input)),
+            );
+        }
+        group.finish();
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bridge_repair
+}
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+//! What the benches share: a way of generating the same large input every
+//! run.
+
+/// A fixed-seed generator, so every run benchmarks the same input.
+pub struct Lcg(pub u64);
+
+impl Lcg {
+    /// The next number, from 0 up to but not including `n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((self.0 >> 33) % n as u64) as usize
+    }
+}
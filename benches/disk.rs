@@ -0,0 +1,52 @@
+//! Benchmarks day 9's whole-file defrag on synthetic disk maps with far
+//! more entries than the puzzle's:
+//!
+//!     cargo bench --bench disk
+use advent_2024::days::day09::PART2_VARIANTS;
+use common::Lcg;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+mod common;
+
+/// A disk map of `entries` digits, alternating files of 1 to 9 blocks and
+/// gaps of 0 to 9.
+fn synthetic_input(entries: usize) -> String {
+    let mut rng = Lcg(2024);
+    (0..entries)
+        .map(|i| {
+            let digit = if i % 2 == 0 {
+                rng.below(9) + 1
+            } else {
+                rng.below(10)
+            };
+            char::from(b'0' + digit as u8)
+        })
+        .collect()
+}
+
+fn disk(c: &mut Criterion) {
+    let mut group = c.benchmark_group("disk/part2");
+    for entries in [20_000, 400_000] {
+        let input = synthetic_input(entries);
+        for variant in PART2_VARIANTS {
+            // The interval set scans the free list for every file, which
+            // takes seconds a run at the larger size.
+            if entries > 20_000 && variant.name == "interval set" {
+                continue;
+            }
+            group.bench_with_input(
+                BenchmarkId::new(variant.name, entries),
+                &input,
+                |b, input| b.iter(|| (variant.run)(input)),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = disk
+}
+criterion_main!(benches);
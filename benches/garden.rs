@@ -0,0 +1,67 @@
+//! Benchmarks day 12's fence prices on large synthetic garden plots, both
+//! of blocks and of speckles, where nearly every region is a tile or two:
+//!
+//!     cargo bench --bench garden
+use advent_2024::days::day12::{PART1_VARIANTS, PART2_VARIANTS};
+use common::Lcg;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+mod common;
+
+/// A `size` by `size` plot of staggered blocks of a few plants, with the
+/// odd stray plant in among them, so regions come in all shapes.
+fn synthetic_input(size: usize) -> String {
+    let mut rng = Lcg(2024);
+    let mut input = String::new();
+    for row in 0..size {
+        for col in 0..size {
+            let plant = if rng.below(10) == 0 {
+                rng.below(4)
+            } else {
+                (row / 4 + (col + row / 8) / 7) % 4
+            };
+            input.push(char::from(b'A' + plant as u8));
+        }
+        input.push('\n');
+    }
+    input
+}
+
+/// A `size` by `size` plot of plants picked at random from the whole
+/// alphabet, so regions are tiny and there are lots of them.
+fn speckled_input(size: usize) -> String {
+    let mut rng = Lcg(2024);
+    let mut input = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            input.push(char::from(b'A' + rng.below(26) as u8));
+        }
+        input.push('\n');
+    }
+    input
+}
+
+fn garden(c: &mut Criterion) {
+    let size = 300;
+    for (plot, input) in [
+        ("blocks", synthetic_input(size)),
+        ("speckled", speckled_input(size)),
+    ] {
+        for (part, variants) in [(1, PART1_VARIANTS), (2, PART2_VARIANTS)] {
+            let mut group = c.benchmark_group(format!("garden/{}/part{}", plot, part));
+            for variant in variants {
+                group.bench_with_input(BenchmarkId::new(variant.name, size), &input, |b, input| {
+                    b.iter(|| (variant.run)(input))
+                });
+            }
+            group.finish();
+        }
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = garden
+}
+criterion_main!(benches);
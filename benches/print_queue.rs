@@ -0,0 +1,61 @@
+//! Benchmarks day 5's ways of checking updates against the rules, on a
+//! synthetic rule set far bigger than the puzzle's:
+//!
+//!     cargo bench --bench print_queue
+use advent_2024::days::day05::{PART1_VARIANTS, PART2_VARIANTS};
+use common::Lcg;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+mod common;
+
+/// Rules ordering every pair of `pages` pages, and `updates` updates of
+/// 25 pages each in random order.
+fn synthetic_input(pages: usize, updates: usize) -> String {
+    let mut rng = Lcg(2024);
+    let mut order: Vec<usize> = (0..pages).collect();
+    for i in (1..pages).rev() {
+        order.swap(i, rng.below(i + 1));
+    }
+    let mut input = String::new();
+    for (i, a) in order.iter().enumerate() {
+        for b in &order[i + 1..] {
+            input += &format!("{}|{}\n", a, b);
+        }
+    }
+    input.push('\n');
+    for _ in 0..updates {
+        let mut update: Vec<usize> = Vec::new();
+        while update.len() < 25 {
+            let page = rng.below(pages);
+            if !update.contains(&page) {
+                update.push(page);
+            }
+        }
+        let update: Vec<String> = update.iter().map(usize::to_string).collect();
+        input += &update.join(",");
+        input.push('\n');
+    }
+    input
+}
+
+fn print_queue(c: &mut Criterion) {
+    let input = synthetic_input(300, 1000);
+    for (part, variants) in [(1, PART1_VARIANTS), (2, PART2_VARIANTS)] {
+        let mut group = c.benchmark_group(format!("print_queue/part{}", part));
+        for variant in variants {
+            group.bench_with_input(
+                BenchmarkId::from_parameter(variant.name),
+                &input,
+                |b, input| b.iter(|| (variant.run)(input)),
+            );
+        }
+        group.finish();
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = print_queue
+}
+criterion_main!(benches);
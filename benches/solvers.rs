@@ -0,0 +1,46 @@
+//! Benchmarks both parts of every day, on the puzzle's example and on the
+//! real input when `inputs/` has it:
+//!
+//!     cargo bench --bench solvers -- day16
+//!
+//! To catch regressions, save a baseline before a change and compare
+//! against it after:
+//!
+//!     cargo bench --bench solvers -- --save-baseline before
+//!     cargo bench --bench solvers -- --baseline before
+use advent_2024::fixtures::sample;
+use advent_2024::runner::{input_path, INPUT_DIR};
+use advent_2024::{Solver, SOLVERS};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::path::Path;
+
+fn bench_parts(c: &mut Criterion, name: &str, solver: &dyn Solver, input: &str) {
+    for part in [1, 2] {
+        // Unimplemented parts just error out; don't bother timing them.
+        if solver.part(part, input).is_err() {
+            continue;
+        }
+        c.bench_function(&format!("{}/part{}", name, part), |b| {
+            b.iter(|| solver.part(part, input))
+        });
+    }
+}
+
+fn solvers(c: &mut Criterion) {
+    for (day, solver) in SOLVERS {
+        if let Some(sample) = sample(*day) {
+            bench_parts(c, &format!("day{:02}/sample", day), *solver, sample.input);
+        }
+        if let Ok(input) = std::fs::read_to_string(input_path(Path::new(INPUT_DIR), *day)) {
+            bench_parts(c, &format!("day{:02}/input", day), *solver, &input);
+        }
+    }
+}
+
+criterion_group! {
+    name = benches;
+    // The slow days take seconds per iteration on real inputs.
+    config = Criterion::default().sample_size(10);
+    targets = solvers
+}
+criterion_main!(benches);
@@ -0,0 +1,44 @@
+//! Benchmarks day 10's trail ratings on large synthetic height maps:
+//!
+//!     cargo bench --bench trails
+use advent_2024::days::day10::PART2_VARIANTS;
+use common::Lcg;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+mod common;
+
+/// A `size` by `size` map rising a step at a time along each diagonal,
+/// with the odd tile out of place, so trails branch and merge
+/// everywhere.
+fn synthetic_input(size: usize) -> String {
+    let mut rng = Lcg(2024);
+    let mut input = String::new();
+    for row in 0..size {
+        for col in 0..size {
+            let height = (row + col + (rng.below(20) == 0) as usize) % 10;
+            input.push(char::from(b'0' + height as u8));
+        }
+        input.push('\n');
+    }
+    input
+}
+
+fn trails(c: &mut Criterion) {
+    let mut group = c.benchmark_group("trails/part2");
+    for size in [100, 500] {
+        let input = synthetic_input(size);
+        for variant in PART2_VARIANTS {
+            group.bench_with_input(BenchmarkId::new(variant.name, size), &input, |b, input| {
+                b.iter(|| (variant.run)(input))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = trails
+}
+criterion_main!(benches);
@@ -0,0 +1,77 @@
+//! Benchmarks `WordSearch` on large synthetic letter grids, against a
+//! reference search that walks by row and column, checking bounds at every
+//! letter, the way day 4 used to:
+//!
+//!     cargo bench --bench wordsearch
+use advent_2024::{Stencil, WordSearch, DIRECTIONS8};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// A `size` by `size` grid of X, M, A and S, from a fixed-seed generator.
+fn synthetic_grid(size: usize) -> String {
+    let mut state: u64 = 2024;
+    let mut text = String::with_capacity(size * (size + 1));
+    for _ in 0..size {
+        for _ in 0..size {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            text.push(b"XMAS"[(state >> 62) as usize] as char);
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// Counts `word` by stepping through `(row, col)` one letter at a time.
+fn count_word_by_row_col(search: &WordSearch, word: &str) -> usize {
+    let letters = search.letters();
+    let mut count = 0;
+    for row in 0..letters.tiles.height {
+        for col in 0..letters.tiles.width {
+            for dir in DIRECTIONS8 {
+                let (delta_row, delta_col) = dir.delta();
+                let mut pos = Some((row, col));
+                let found = word.chars().all(|ch| {
+                    let Some((row, col)) = pos else {
+                        return false;
+                    };
+                    pos = row
+                        .checked_add_signed(delta_row)
+                        .zip(col.checked_add_signed(delta_col));
+                    letters.get(row, col) == Some(&ch)
+                });
+                count += found as usize;
+            }
+        }
+    }
+    count
+}
+
+fn word_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("wordsearch");
+    let cross = Stencil::parse("M.S\n.A.\nM.S").unwrap().rotations();
+    for size in [140, 1000] {
+        let search = WordSearch::parse(&synthetic_grid(size)).unwrap();
+        assert_eq!(
+            search.count_word("XMAS"),
+            count_word_by_row_col(&search, "XMAS")
+        );
+        group.bench_with_input(BenchmarkId::new("flat", size), &search, |b, search| {
+            b.iter(|| search.count_word("XMAS"))
+        });
+        group.bench_with_input(BenchmarkId::new("row-col", size), &search, |b, search| {
+            b.iter(|| count_word_by_row_col(search, "XMAS"))
+        });
+        group.bench_with_input(BenchmarkId::new("stencils", size), &search, |b, search| {
+            b.iter(|| search.count_stencils(&cross))
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = word_search
+}
+criterion_main!(benches);
@@ -0,0 +1,335 @@
+//! Talks to adventofcode.com.  Requests are authenticated with the session
+//! cookie from a logged-in browser, taken from the `AOC_SESSION`
+//! environment variable.
+//!
+//! To go easy on the site, inputs are only ever downloaded once, answers
+//! are only ever submitted once, and requests are spaced at least
+//! `MIN_REQUEST_INTERVAL` apart.
+use crate::runner::input_path;
+use std::error::Error;
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+const YEAR: u32 = 2024;
+const USER_AGENT: &str = "github.com/dyoo/advent-2024-rust";
+/// Every submission and its outcome, one tab-separated line each.
+pub const SUBMISSIONS_FILE: &str = "submissions.tsv";
+/// Touched on every request, so the interval holds across runs.
+const LAST_REQUEST_FILE: &str = ".last-request";
+
+pub fn input_url(day: u8) -> String {
+    format!("https://adventofcode.com/{}/day/{}/input", YEAR, day)
+}
+
+fn session() -> Result<String, Box<dyn Error>> {
+    std::env::var(SESSION_VAR).map_err(|_| {
+        format!(
+            "Set {} to your adventofcode.com session cookie",
+            SESSION_VAR
+        )
+        .into()
+    })
+}
+
+/// How much longer to wait before making another request.
+fn remaining_wait(last_request: SystemTime, now: SystemTime) -> Duration {
+    let elapsed = now.duration_since(last_request).unwrap_or_default();
+    MIN_REQUEST_INTERVAL.saturating_sub(elapsed)
+}
+
+/// Sleeps until it's polite to make another request, then records that
+/// one is being made.
+fn throttle(dir: &Path) -> std::io::Result<()> {
+    let stamp = dir.join(LAST_REQUEST_FILE);
+    if let Ok(last_request) = std::fs::metadata(&stamp).and_then(|m| m.modified()) {
+        std::thread::sleep(remaining_wait(last_request, SystemTime::now()));
+    }
+    std::fs::write(stamp, "")
+}
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new().user_agent(USER_AGENT).build()
+}
+
+/// Returns `day`'s input from `dir`, downloading it there first if needed.
+pub fn load_input(dir: &Path, day: u8) -> Result<String, Box<dyn Error>> {
+    let path = input_path(dir, day);
+    if let Ok(input) = std::fs::read_to_string(&path) {
+        return Ok(input);
+    }
+
+    let session = session()?;
+    std::fs::create_dir_all(dir)?;
+    throttle(dir)?;
+    let input = agent()
+        .get(&input_url(day))
+        .set("Cookie", &format!("session={}", session))
+        .call()?
+        .into_string()?;
+    std::fs::write(&path, &input)?;
+    Ok(input)
+}
+
+pub fn answer_url(day: u8) -> String {
+    format!("https://adventofcode.com/{}/day/{}/answer", YEAR, day)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Submitted too soon after the last wrong answer; holds the wait.
+    Wait(String),
+    /// The part was already solved, or part 1 hasn't been yet.
+    WrongLevel,
+    Unrecognized,
+}
+
+impl Outcome {
+    /// Whether this settles the answer, and so is worth remembering.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Correct | Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong
+        )
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "too high"),
+            Outcome::TooLow => write!(f, "too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::Wait(wait) => write!(f, "wait {}", wait),
+            Outcome::WrongLevel => write!(f, "wrong level"),
+            Outcome::Unrecognized => write!(f, "unrecognized response"),
+        }
+    }
+}
+
+/// Reads the outcome out of the page returned for a submission.
+pub fn parse_outcome(page: &str) -> Outcome {
+    if page.contains("That's the right answer") {
+        Outcome::Correct
+    } else if page.contains("That's not the right answer") {
+        if page.contains("too high") {
+            Outcome::TooHigh
+        } else if page.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if page.contains("You gave an answer too recently") {
+        let wait = page
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map_or("a while", |(wait, _)| wait);
+        Outcome::Wait(wait.to_string())
+    } else if page.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unrecognized
+    }
+}
+
+fn parse_final_outcome(s: &str) -> Option<Outcome> {
+    [
+        Outcome::Correct,
+        Outcome::TooHigh,
+        Outcome::TooLow,
+        Outcome::Wrong,
+    ]
+    .into_iter()
+    .find(|outcome| outcome.to_string() == s)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Submission {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/// Past submissions with a final outcome, as recorded in
+/// `SUBMISSIONS_FILE`.
+#[derive(Debug, Default, PartialEq)]
+pub struct Submissions(pub Vec<Submission>);
+
+impl Submissions {
+    pub fn parse(text: &str) -> Result<Submissions, String> {
+        text.lines()
+            .map(|line| {
+                let bad_line = || format!("Bad submission line: {:?}", line);
+                let [day, part, answer, outcome] = line.split('\t').collect::<Vec<_>>()[..] else {
+                    return Err(bad_line());
+                };
+                Ok(Submission {
+                    day: day.parse().map_err(|_| bad_line())?,
+                    part: part.parse().map_err(|_| bad_line())?,
+                    answer: answer.to_string(),
+                    outcome: parse_final_outcome(outcome).ok_or_else(bad_line)?,
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(Submissions)
+    }
+
+    pub fn load(dir: &Path) -> Result<Submissions, Box<dyn Error>> {
+        match std::fs::read_to_string(dir.join(SUBMISSIONS_FILE)) {
+            Ok(text) => Ok(Submissions::parse(&text)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// The accepted answer for this part, if there is one.
+    pub fn solution(&self, day: u8, part: u8) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|s| s.day == day && s.part == part && s.outcome == Outcome::Correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    /// What happened when `answer` was submitted before, if it was.
+    pub fn previous(&self, day: u8, part: u8, answer: &str) -> Option<&Outcome> {
+        self.0
+            .iter()
+            .find(|s| s.day == day && s.part == part && s.answer == answer)
+            .map(|s| &s.outcome)
+    }
+}
+
+fn record(dir: &Path, submission: &Submission) -> std::io::Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(SUBMISSIONS_FILE))?;
+    writeln!(
+        file,
+        "{}\t{}\t{}\t{}",
+        submission.day, submission.part, submission.answer, submission.outcome
+    )
+}
+
+/// Submits `answer` for `day`'s `part`, unless it's been settled already.
+/// Final outcomes are recorded in `dir`.
+pub fn submit(dir: &Path, day: u8, part: u8, answer: &str) -> Result<Outcome, Box<dyn Error>> {
+    let submissions = Submissions::load(dir)?;
+    if let Some(solution) = submissions.solution(day, part) {
+        return Err(format!("Already solved, with {}", solution).into());
+    }
+    if let Some(outcome) = submissions.previous(day, part, answer) {
+        return Err(format!("Already submitted {}: {}", answer, outcome).into());
+    }
+
+    let session = session()?;
+    std::fs::create_dir_all(dir)?;
+    throttle(dir)?;
+    let page = agent()
+        .post(&answer_url(day))
+        .set("Cookie", &format!("session={}", session))
+        .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+        .into_string()?;
+    let outcome = parse_outcome(&page);
+    if outcome.is_final() {
+        record(
+            dir,
+            &Submission {
+                day,
+                part,
+                answer: answer.to_string(),
+                outcome: outcome.clone(),
+            },
+        )?;
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_input_url() -> Result<()> {
+        verify_that!(
+            input_url(12),
+            eq("https://adventofcode.com/2024/day/12/input")
+        )
+    }
+
+    #[gtest]
+    fn test_parse_outcome() -> Result<()> {
+        verify_that!(
+            parse_outcome("<p>That's the right answer!  You are one gold star closer"),
+            eq(&Outcome::Correct)
+        )?;
+        verify_that!(
+            parse_outcome("<p>That's not the right answer; your answer is too high."),
+            eq(&Outcome::TooHigh)
+        )?;
+        verify_that!(
+            parse_outcome("<p>That's not the right answer; your answer is too low."),
+            eq(&Outcome::TooLow)
+        )?;
+        verify_that!(
+            parse_outcome("<p>That's not the right answer.  If you're stuck"),
+            eq(&Outcome::Wrong)
+        )?;
+        verify_that!(
+            parse_outcome(
+                "<p>You gave an answer too recently; you have to wait after submitting \
+                 an answer before trying again.  You have 37s left to wait."
+            ),
+            eq(&Outcome::Wait("37s".into()))
+        )?;
+        verify_that!(
+            parse_outcome("<p>You don't seem to be solving the right level."),
+            eq(&Outcome::WrongLevel)
+        )?;
+        verify_that!(parse_outcome("<html>"), eq(&Outcome::Unrecognized))
+    }
+
+    #[gtest]
+    fn test_submissions() -> Result<()> {
+        let submissions = Submissions::parse("1\t1\t100\ttoo high\n1\t1\t42\tcorrect\n").unwrap();
+        verify_that!(submissions.solution(1, 1), some(eq("42")))?;
+        verify_that!(submissions.solution(1, 2), none())?;
+        verify_that!(
+            submissions.previous(1, 1, "100"),
+            some(eq(&Outcome::TooHigh))
+        )?;
+        verify_that!(submissions.previous(1, 1, "99"), none())?;
+        verify_that!(Submissions::parse("1\t1\t100"), err(anything()))?;
+        verify_that!(Submissions::parse("1\t1\t100\twait 5s"), err(anything()))
+    }
+
+    #[gtest]
+    fn test_remaining_wait() -> Result<()> {
+        let last = SystemTime::UNIX_EPOCH;
+        verify_that!(remaining_wait(last, last), eq(MIN_REQUEST_INTERVAL))?;
+        verify_that!(
+            remaining_wait(last, last + Duration::from_secs(2)),
+            eq(Duration::from_secs(3))
+        )?;
+        verify_that!(
+            remaining_wait(last, last + Duration::from_secs(60)),
+            eq(Duration::ZERO)
+        )?;
+        // A clock that went backwards shouldn't wait forever.
+        verify_that!(
+            remaining_wait(last + Duration::from_secs(60), last),
+            eq(MIN_REQUEST_INTERVAL)
+        )
+    }
+}
@@ -0,0 +1,365 @@
+//! Runs any day's solution from one place:
+//!
+//!     cargo run --bin advent -- --day 16 --part 2 < input.txt
+//!
+//! Without piped input, the day's file in `inputs/` is used, downloaded
+//! first if `AOC_SESSION` is set (see `advent_2024::aoc`).
+//!
+//! or every day at once, timing each part:
+//!
+//!     cargo run --release --bin advent -- all
+//!
+//! or write the same results, with notes on each day, to a Markdown report:
+//!
+//!     cargo run --release --bin advent -- report [report.md]
+//!
+//! or check every answer in `answers.toml`, exiting non-zero on a mismatch:
+//!
+//!     cargo run --release --bin advent -- verify [answers.toml]
+//!
+//! or run every alternative implementation of a part side by side,
+//! exiting non-zero if they disagree:
+//!
+//!     cargo run --release --bin advent -- compare
+//!
+//! or submit an answer computed from `inputs/`, recording the outcome
+//! there:
+//!
+//!     cargo run --release --bin advent -- submit --day 16 --part 2
+//!
+//! or start on a new day:
+//!
+//!     cargo run --bin advent -- new --day 21
+//!
+//! Add `--profile` to a day's run to write a flamegraph per part to
+//! `profiles/` (see `advent_2024::profile`).
+//!
+//! Days are looked up in the library's solver registry.
+use advent_2024::aoc::{load_input, submit};
+use advent_2024::cli::{parse_part, parse_verbosity};
+use advent_2024::fixtures::print_sample_answers;
+use advent_2024::profile::{flamegraph_path, profiled, PROFILE_DIR};
+use advent_2024::report::{markdown_report, REPORT_FILE};
+use advent_2024::runner::{format_table, input_path, run_all, INPUT_DIR};
+use advent_2024::scaffold::scaffold;
+use advent_2024::variants::{compare, format_comparison, ALTERNATIVES};
+use advent_2024::verify::{format_check, parse_answers, verify, ANSWERS_FILE};
+use advent_2024::{print_answers, solver};
+use std::error::Error;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+const LAST_DAY: u8 = 25;
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    /// Run one day on stdin.
+    Day(Args),
+    /// Run every day on its file in `inputs/`.
+    All,
+    /// Run every day and write a Markdown report to the given file.
+    Report(PathBuf),
+    /// Compare alternative implementations on `inputs/`.
+    Compare,
+    /// Check answers against the given answers file.
+    Verify(PathBuf),
+    /// Generate the files for a new day.
+    New { day: u8 },
+    /// Submit one part's answer to adventofcode.com.
+    Submit { day: u8, part: u8 },
+}
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    day: Option<u8>,
+    part: Option<u8>,
+    sample: bool,
+    profile: bool,
+}
+
+fn parse_command(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter().peekable();
+    match args.peek().map(String::as_str) {
+        Some("all") => match args.nth(1) {
+            Some(arg) => Err(format!("Unknown argument: {:?}", arg)),
+            None => Ok(Command::All),
+        },
+        Some("compare") => match args.nth(1) {
+            Some(arg) => Err(format!("Unknown argument: {:?}", arg)),
+            None => Ok(Command::Compare),
+        },
+        Some("report") => {
+            let path = args.nth(1).unwrap_or_else(|| REPORT_FILE.to_string());
+            match args.next() {
+                Some(arg) => Err(format!("Unknown argument: {:?}", arg)),
+                None => Ok(Command::Report(path.into())),
+            }
+        }
+        Some("verify") => {
+            let path = args.nth(1).unwrap_or_else(|| ANSWERS_FILE.to_string());
+            match args.next() {
+                Some(arg) => Err(format!("Unknown argument: {:?}", arg)),
+                None => Ok(Command::Verify(path.into())),
+            }
+        }
+        Some("new") => {
+            args.next();
+            match parse_args(args)? {
+                Args {
+                    day: Some(day),
+                    part: None,
+                    sample: false,
+                    profile: false,
+                } => Ok(Command::New { day }),
+                _ => Err("new needs just --day N".to_string()),
+            }
+        }
+        Some("submit") => {
+            args.next();
+            match parse_args(args)? {
+                Args {
+                    day: Some(day),
+                    part: Some(part),
+                    sample: false,
+                    profile: false,
+                } => Ok(Command::Submit { day, part }),
+                _ => Err("submit needs --day N and --part 1|2".to_string()),
+            }
+        }
+        _ => parse_args(args).map(Command::Day),
+    }
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args {
+        day: None,
+        part: None,
+        sample: false,
+        profile: false,
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => {
+                let day = args.next().ok_or("--day needs a value")?;
+                let day: u8 = day
+                    .parse()
+                    .map_err(|_| format!("Bad day number: {:?}", day))?;
+                if !(1..=LAST_DAY).contains(&day) {
+                    return Err(format!("Day must be between 1 and {}", LAST_DAY));
+                }
+                parsed.day = Some(day);
+            }
+            "--part" => {
+                parsed.part = parse_part(&args.next().ok_or("--part needs a value")?)?;
+            }
+            "--sample" => parsed.sample = true,
+            "--profile" => parsed.profile = true,
+            _ => return Err(format!("Unknown argument: {:?}", arg)),
+        }
+    }
+    Ok(parsed)
+}
+
+fn unimplemented_days() -> Vec<u8> {
+    (1..=LAST_DAY)
+        .filter(|day| solver(*day).is_none())
+        .collect()
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    // -v and friends work with every command.
+    let (verbosity, args): (Vec<String>, Vec<String>) = std::env::args()
+        .skip(1)
+        .partition(|arg| parse_verbosity(arg).is_some());
+    advent_2024::logging::init(verbosity.iter().filter_map(|v| parse_verbosity(v)).sum());
+
+    let args = match parse_command(args)? {
+        Command::All => {
+            print!("{}", format_table(&run_all(Path::new(INPUT_DIR))));
+            return Ok(());
+        }
+        Command::Report(path) => {
+            std::fs::write(&path, markdown_report(&run_all(Path::new(INPUT_DIR))))?;
+            println!("Wrote {}", path.display());
+            return Ok(());
+        }
+        Command::Compare => {
+            let mut all_agree = true;
+            for (day, part, variants) in ALTERNATIVES {
+                let Ok(input) = std::fs::read_to_string(input_path(Path::new(INPUT_DIR), *day))
+                else {
+                    continue;
+                };
+                let comparison = compare(*day, *part, variants, &input);
+                print!("{}", format_comparison(&comparison));
+                all_agree &= comparison.agrees();
+            }
+            if !all_agree {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Command::Verify(path) => {
+            let answers = parse_answers(&std::fs::read_to_string(path)?)?;
+            let checks = verify(&answers, Path::new(INPUT_DIR));
+            for check in &checks {
+                println!("{}", format_check(check));
+            }
+            let failures = checks.iter().filter(|check| !check.passed()).count();
+            println!("{} of {} passed", checks.len() - failures, checks.len());
+            if failures > 0 {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Command::New { day } => {
+            for path in scaffold(Path::new(env!("CARGO_MANIFEST_DIR")), day)? {
+                println!("Wrote {}", path.display());
+            }
+            return Ok(());
+        }
+        Command::Submit { day, part } => {
+            let solver =
+                solver(day).ok_or_else(|| format!("Day {} is not implemented yet", day))?;
+            let input = load_input(Path::new(INPUT_DIR), day)?;
+            let answer = solver.part(part, &input)?;
+            println!("Submitting {}", answer);
+            println!("{}", submit(Path::new(INPUT_DIR), day, part, &answer)?);
+            return Ok(());
+        }
+        Command::Day(args) => args,
+    };
+
+    let Some(day) = args.day else {
+        println!(
+            "Usage: advent [-v|-vv] --day N [--part 1|2|all] [--sample] [--profile] [< input]"
+        );
+        println!("       advent all");
+        println!("       advent report [report.md]");
+        println!("       advent compare");
+        println!("       advent verify [answers.toml]");
+        println!("       advent submit --day N --part 1|2");
+        println!("       advent new --day N");
+        let missing: Vec<String> = unimplemented_days().iter().map(u8::to_string).collect();
+        println!("Not yet implemented: {}", missing.join(", "));
+        return Ok(());
+    };
+    let solver = solver(day).ok_or_else(|| format!("Day {} is not implemented yet", day))?;
+
+    if args.sample {
+        return print_sample_answers(solver, day, args.part);
+    }
+    let input = if std::io::stdin().is_terminal() {
+        load_input(Path::new(INPUT_DIR), day)?
+    } else {
+        std::io::read_to_string(std::io::stdin())?
+    };
+    if args.profile {
+        for part in [1, 2] {
+            if args.part.is_none_or(|p| p == part) {
+                let path = flamegraph_path(Path::new(PROFILE_DIR), day, part);
+                let answer = profiled(&path, || solver.part(part, &input))?;
+                println!("Part {}: {}", part, answer);
+                eprintln!("Wrote {}", path.display());
+            }
+        }
+        return Ok(());
+    }
+    print_answers(solver, &input, args.part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_2024::SOLVERS;
+    use googletest::prelude::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[gtest]
+    fn test_parse_args() -> Result<()> {
+        verify_that!(
+            parse_args(args("--day 16 --part 2")),
+            ok(eq(&Args {
+                day: Some(16),
+                part: Some(2),
+                sample: false,
+                profile: false,
+            }))
+        )?;
+        verify_that!(
+            parse_args(args("--day 3")),
+            ok(eq(&Args {
+                day: Some(3),
+                part: None,
+                sample: false,
+                profile: false,
+            }))
+        )?;
+        verify_that!(
+            parse_args(args("--day 6 --part 2 --profile")),
+            ok(eq(&Args {
+                day: Some(6),
+                part: Some(2),
+                sample: false,
+                profile: true,
+            }))
+        )?;
+        verify_that!(parse_args(args("--day 26")), err(anything()))?;
+        verify_that!(parse_args(args("--part 3")), err(anything()))?;
+        verify_that!(parse_args(args("--day")), err(anything()))?;
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_parse_command() -> Result<()> {
+        verify_that!(parse_command(args("all")), ok(eq(&Command::All)))?;
+        verify_that!(parse_command(args("all --day 3")), err(anything()))?;
+        verify_that!(parse_command(args("compare")), ok(eq(&Command::Compare)))?;
+        verify_that!(
+            parse_command(args("report")),
+            ok(eq(&Command::Report(REPORT_FILE.into())))
+        )?;
+        verify_that!(
+            parse_command(args("verify")),
+            ok(eq(&Command::Verify(ANSWERS_FILE.into())))
+        )?;
+        verify_that!(
+            parse_command(args("verify mine.toml")),
+            ok(eq(&Command::Verify("mine.toml".into())))
+        )?;
+        verify_that!(
+            parse_command(args("submit --day 3 --part 2")),
+            ok(eq(&Command::Submit { day: 3, part: 2 }))
+        )?;
+        verify_that!(parse_command(args("submit --day 3")), err(anything()))?;
+        verify_that!(
+            parse_command(args("new --day 21")),
+            ok(eq(&Command::New { day: 21 }))
+        )?;
+        verify_that!(parse_command(args("new")), err(anything()))?;
+        verify_that!(
+            parse_command(args("submit --day 3 --part all")),
+            err(anything())
+        )?;
+        verify_that!(
+            parse_command(args("--day 3")),
+            ok(eq(&Command::Day(Args {
+                day: Some(3),
+                part: None,
+                sample: false,
+                profile: false,
+            })))
+        )
+    }
+
+    #[gtest]
+    fn test_unimplemented_days() -> Result<()> {
+        let missing = unimplemented_days();
+        verify_that!(missing.len(), eq(LAST_DAY as usize - SOLVERS.len()))?;
+        verify_that!(missing, each(predicate(|day: &u8| solver(*day).is_none())))
+    }
+}
@@ -1,78 +1,17 @@
-use std::collections::HashMap;
-
-fn parse(s: &str) -> impl Iterator<Item = Result<u64, std::num::ParseIntError>> + '_ {
-    s.split_whitespace().map(|s| s.parse::<u64>())
-}
-
-fn blink_all(numbers: impl IntoIterator<Item = u64>) -> Vec<u64> {
-    numbers.into_iter().flat_map(blink).collect()
-}
-
-#[test]
-fn test_blink_all() {
-    assert_eq!(blink_all([125, 17]), vec![253000, 1, 7]);
-    assert_eq!(blink_all([253000, 1, 7]), vec![253, 0, 2024, 14168]);
-}
-
-fn blink(number: u64) -> Vec<u64> {
-    match number {
-        0 => vec![1],
-        n if even_digits(n) => split_halves(n),
-        _ => vec![number.checked_mul(2024).expect("overflow")],
-    }
-}
-
-fn even_digits(number: u64) -> bool {
-    (number.ilog10() + 1) % 2 == 0
-}
-
-fn split_halves(number: u64) -> Vec<u64> {
-    let modulo = 10u64.pow((number.ilog10() + 1) / 2);
-    vec![number / modulo, number % modulo]
-}
-
-fn histogram(numbers: impl IntoIterator<Item = u64>) -> HashMap<u64, u64> {
-    let mut map = HashMap::new();
-    for n in numbers {
-        *map.entry(n).or_default() += 1;
-    }
-    map
-}
-
-fn blink_all_counting(numbers: HashMap<u64, u64>) -> HashMap<u64, u64> {
-    let mut map = HashMap::new();
-    for (n, factor) in numbers {
-        for v in blink(n) {
-            *map.entry(v).or_default() += factor;
-        }
-    }
-    map
-}
+use advent_2024::days::day11::{count_after, parse_stones, Day11};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let input = std::io::read_to_string(std::io::stdin())?;
-
-    {
-        let mut values: Vec<u64> = parse(&input).collect::<Result<Vec<_>, _>>()?;
-        for _ in 0..25 {
-            values = blink_all(values);
-        }
-        println!("Part 1: {:?}", values.len());
-
-        let mut values_map: HashMap<u64, u64> =
-            histogram(parse(&input).collect::<Result<Vec<_>, _>>()?);
-        for _ in 0..25 {
-            values_map = blink_all_counting(values_map);
-        }
-        println!("Part 1: {:?}", values_map.values().sum::<u64>());
-
-        let mut values_map: HashMap<u64, u64> =
-            histogram(parse(&input).collect::<Result<Vec<_>, _>>()?);
-        for _ in 0..75 {
-            values_map = blink_all_counting(values_map);
-        }
-        println!("Part 2: {:?}", values_map.values().sum::<u64>());
+    let args = advent_2024::cli::Args::from_env(&["--blinks="])?;
+    advent_2024::logging::init(args.verbosity);
+    if args.sample {
+        return advent_2024::fixtures::print_sample_answers(&Day11, 11, args.part);
     }
-
-    Ok(())
+    let input = args.read_input()?;
+    if let Some(blinks) = args.flag_value("--blinks") {
+        let blinks: usize = blinks.parse()?;
+        let stones = count_after(&parse_stones(&input)?, blinks)?;
+        println!("Stones after {} blinks: {}", blinks, stones);
+        return Ok(());
+    }
+    advent_2024::print_answers(&Day11, &input, args.part)
 }
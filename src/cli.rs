@@ -0,0 +1,227 @@
+//! Command-line handling shared by the day binaries.
+//!
+//! ```text
+//! cargo run --bin day15 -- inputs/day15.txt
+//! cargo run --bin day15 < inputs/day15.txt
+//! cargo run --bin day06 -- --part 1 inputs/day06.txt
+//! cargo run --bin day16 -- --sample
+//! cargo run --bin day16 -- -vv inputs/day16.txt
+//! cargo run --bin day02 -- --tolerance=2 inputs/day02.txt
+//! ```
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Args {
+    /// Where to read the puzzle input from; stdin if absent.
+    pub input: Option<PathBuf>,
+    /// Which part to run; both if absent.
+    pub part: Option<u8>,
+    /// Run on the puzzle's worked example instead of an input.
+    pub sample: bool,
+    /// How many `-v`s were given; see `logging::level_filter`.
+    pub verbosity: u8,
+    /// The binary's own flags, from those it accepts, left for it to
+    /// interpret.
+    pub flags: Vec<String>,
+}
+
+impl Args {
+    /// The first bare argument is the input path; the rest must be the
+    /// common flags or one of `accepted`, the binary's own.  A flag that
+    /// takes a value is listed with a trailing `=`, like `--tolerance=`,
+    /// and must be given as `--tolerance=2`.
+    pub fn parse(
+        args: impl IntoIterator<Item = String>,
+        accepted: &[&str],
+    ) -> Result<Args, String> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "--part" {
+                parsed.part = parse_part(&args.next().ok_or("--part needs a value")?)?;
+            } else if let Some(verbosity) = parse_verbosity(&arg) {
+                parsed.verbosity += verbosity;
+            } else if arg == "--sample" {
+                parsed.sample = true;
+            } else if arg.starts_with("--") {
+                check_flag(&arg, accepted)?;
+                parsed.flags.push(arg);
+            } else if parsed.input.is_none() {
+                parsed.input = Some(arg.into());
+            } else {
+                return Err(format!("Unexpected argument: {:?}", arg));
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// The command line's arguments, accepting `accepted` as in `parse`.
+    pub fn from_env(accepted: &[&str]) -> Result<Args, String> {
+        Args::parse(std::env::args().skip(1), accepted)
+    }
+
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|f| f == flag)
+    }
+
+    /// The value of a flag given as `--name=value`.
+    pub fn flag_value(&self, flag: &str) -> Option<&str> {
+        self.flags
+            .iter()
+            .find_map(|f| f.strip_prefix(flag)?.strip_prefix('='))
+    }
+
+    pub fn read_input(&self) -> std::io::Result<String> {
+        match &self.input {
+            Some(path) => std::fs::read_to_string(path),
+            None => std::io::read_to_string(std::io::stdin()),
+        }
+    }
+
+    /// The input as a buffered reader, for days that can work through it
+    /// a line at a time rather than reading it all up front.
+    pub fn open_input(&self) -> std::io::Result<Box<dyn BufRead>> {
+        Ok(match &self.input {
+            Some(path) => Box::new(BufReader::new(File::open(path)?)),
+            None => Box::new(std::io::stdin().lock()),
+        })
+    }
+}
+
+/// Whether `flag` is one of `accepted`, with a value just when it should
+/// have one.
+fn check_flag(flag: &str, accepted: &[&str]) -> Result<(), String> {
+    let ok = match flag.split_once('=') {
+        Some((name, _)) => accepted.iter().any(|a| a.strip_suffix('=') == Some(name)),
+        None => accepted.contains(&flag),
+    };
+    if ok {
+        Ok(())
+    } else if accepted.contains(&format!("{}=", flag).as_str()) {
+        Err(format!("{} needs a value, like {}=N", flag, flag))
+    } else if accepted.is_empty() {
+        Err(format!(
+            "Unknown flag {}; this day has no flags of its own",
+            flag
+        ))
+    } else {
+        Err(format!(
+            "Unknown flag {}; this day takes {}",
+            flag,
+            accepted.join(", ")
+        ))
+    }
+}
+
+/// Counts the `v`s in `-v`, `-vv` and so on; `--verbose` counts as one.
+pub fn parse_verbosity(arg: &str) -> Option<u8> {
+    if arg == "--verbose" {
+        return Some(1);
+    }
+    let vs = arg.strip_prefix('-')?;
+    (!vs.is_empty() && vs.chars().all(|c| c == 'v')).then_some(vs.len() as u8)
+}
+
+/// Parses the value of a `--part` flag: `1`, `2`, or `all` (`None`).
+pub fn parse_part(s: &str) -> Result<Option<u8>, String> {
+    match s {
+        "1" => Ok(Some(1)),
+        "2" => Ok(Some(2)),
+        "all" => Ok(None),
+        _ => Err(format!("Part must be 1, 2 or all, not {:?}", s)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    const FLAGS: &[&str] = &["--explain", "--tolerance="];
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[gtest]
+    fn test_parse() -> Result<()> {
+        verify_that!(Args::parse(args(""), FLAGS), ok(eq(&Args::default())))?;
+        verify_that!(
+            Args::parse(args("--explain inputs/day13.txt"), FLAGS),
+            ok(eq(&Args {
+                input: Some("inputs/day13.txt".into()),
+                part: None,
+                sample: false,
+                verbosity: 0,
+                flags: vec!["--explain".into()],
+            }))
+        )?;
+        verify_that!(
+            Args::parse(args("--sample"), FLAGS),
+            ok(field!(Args.sample, eq(&true)))
+        )?;
+        verify_that!(Args::parse(args("a.txt b.txt"), FLAGS), err(anything()))?;
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_parse_part() -> Result<()> {
+        verify_that!(
+            Args::parse(args("--part 2 in.txt"), FLAGS),
+            ok(eq(&Args {
+                input: Some("in.txt".into()),
+                part: Some(2),
+                sample: false,
+                verbosity: 0,
+                flags: vec![],
+            }))
+        )?;
+        verify_that!(
+            Args::parse(args("--part all"), FLAGS),
+            ok(field!(Args.part, none()))
+        )?;
+        verify_that!(Args::parse(args("--part 3"), FLAGS), err(anything()))?;
+        verify_that!(Args::parse(args("--part"), FLAGS), err(anything()))
+    }
+
+    #[gtest]
+    fn test_parse_verbosity() -> Result<()> {
+        verify_that!(parse_verbosity("-v"), some(eq(1)))?;
+        verify_that!(parse_verbosity("-vv"), some(eq(2)))?;
+        verify_that!(parse_verbosity("--verbose"), some(eq(1)))?;
+        verify_that!(parse_verbosity("-"), none())?;
+        verify_that!(parse_verbosity("-x"), none())?;
+        verify_that!(
+            Args::parse(args("-v in.txt -v"), FLAGS),
+            ok(field!(Args.verbosity, eq(&2)))
+        )
+    }
+
+    #[gtest]
+    fn test_has_flag() -> Result<()> {
+        let parsed = Args::parse(args("--explain"), FLAGS).unwrap();
+        verify_that!(parsed.has_flag("--explain"), eq(true))?;
+        verify_that!(parsed.has_flag("--verbose"), eq(false))
+    }
+
+    #[gtest]
+    fn test_unknown_flag() -> Result<()> {
+        verify_that!(Args::parse(args("--explian"), FLAGS), err(anything()))?;
+        verify_that!(Args::parse(args("--explain"), &[]), err(anything()))?;
+        verify_that!(Args::parse(args("--explain=yes"), FLAGS), err(anything()))?;
+        verify_that!(
+            Args::parse(args("--tolerance 2"), FLAGS),
+            err(eq("--tolerance needs a value, like --tolerance=N"))
+        )
+    }
+
+    #[gtest]
+    fn test_flag_value() -> Result<()> {
+        let parsed = Args::parse(args("--tolerance=2 --explain in.txt"), FLAGS).unwrap();
+        verify_that!(parsed.flag_value("--tolerance"), some(eq("2")))?;
+        verify_that!(parsed.flag_value("--explain"), none())?;
+        verify_that!(parsed.flag_value("--tol"), none())
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{AdventError, Solver};
+use std::collections::HashMap;
+
+fn distance(xs: impl IntoIterator<Item = u32>, ys: impl IntoIterator<Item = u32>) -> u32 {
+    let mut xs: Vec<_> = xs.into_iter().collect();
+    let mut ys: Vec<_> = ys.into_iter().collect();
+    xs.sort();
+    ys.sort();
+    xs.into_iter().zip(ys).map(|(x, y)| x.abs_diff(y)).sum()
+}
+
+fn similarity(xs: impl IntoIterator<Item = u32>, ys: impl IntoIterator<Item = u32>) -> u32 {
+    let mut counts: HashMap<u32, u32> = HashMap::new();
+    for y in ys {
+        *counts.entry(y).or_default() += 1;
+    }
+    xs.into_iter()
+        .map(|x| x * counts.get(&x).unwrap_or(&0))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_distance() -> Result<()> {
+        let xs = [3, 4, 2, 1, 3, 3];
+        let ys = [4, 3, 5, 3, 9, 3];
+        verify_that!(distance(xs, ys), eq(11))
+    }
+
+    #[gtest]
+    fn test_similarity() -> Result<()> {
+        let xs = [3, 4, 2, 1, 3, 3];
+        let ys = [4, 3, 5, 3, 9, 3];
+        verify_that!(similarity(xs, ys), eq(31))
+    }
+
+    #[gtest]
+    fn test_parse_two_columns() -> Result<()> {
+        let expected = (vec![3, 4, 2], vec![4, 3, 5]);
+        verify_that!(parse_two_columns("3   4\n4   3\n2   5\n")?, eq(&expected))?;
+        verify_that!(
+            parse_two_columns("3\t4\r\n\n4,3\n  2 ,  5  \n\n")?,
+            eq(&expected)
+        )?;
+        verify_that!(parse_two_columns("")?, eq(&(vec![], vec![])))
+    }
+
+    #[gtest]
+    fn test_parse_two_columns_errors() -> Result<()> {
+        verify_that!(
+            parse_two_columns("3 4\n\n5\n").map_err(|e| e.to_string()),
+            err(eq("Parse error: Line 3: expected 2 columns, found \"5\""))
+        )?;
+        verify_that!(
+            parse_two_columns("3 4 5").map_err(|e| e.to_string()),
+            err(eq(
+                "Parse error: Line 1: expected 2 columns, found \"3 4 5\""
+            ))
+        )?;
+        verify_that!(
+            parse_two_columns("3 x").map_err(|e| e.to_string()),
+            err(eq(
+                "Parse error: Line 1: \"x\": invalid digit found in string"
+            ))
+        )
+    }
+
+    #[gtest]
+    fn test_parse_columns() -> Result<()> {
+        verify_that!(
+            parse_columns("1 2 3\n\n4,5,6\n")?,
+            eq(&vec![vec![1, 4], vec![2, 5], vec![3, 6]])
+        )?;
+        verify_that!(
+            parse_columns("1 2 3\n4 5\n").map_err(|e| e.to_string()),
+            err(eq("Parse error: Line 2: expected 3 columns, found \"4 5\""))
+        )
+    }
+
+    #[gtest]
+    fn test_column_report() -> Result<()> {
+        let columns = vec![
+            vec![3, 4, 2, 1, 3, 3],
+            vec![4, 3, 5, 3, 9, 3],
+            vec![3, 3, 3, 3, 3, 3],
+        ];
+        let report = ColumnReport::new(&columns);
+        verify_that!(
+            report.distance,
+            eq(&vec![vec![0, 11, 4], vec![11, 0, 9], vec![4, 9, 0]])
+        )?;
+        verify_that!(report.similarity[0][1], eq(31))?;
+        verify_that!(report.similarity[0][2], eq(54))?;
+        verify_that!(report.similarity[2][1], eq(54))
+    }
+}
+
+/// Distance and similarity between every pair of columns, for inputs
+/// with more lists to reconcile than the puzzle's two.  Entry `[i][j]` of
+/// each table compares column `i` with column `j`.
+#[derive(Debug, PartialEq)]
+pub struct ColumnReport {
+    pub distance: Vec<Vec<u32>>,
+    pub similarity: Vec<Vec<u32>>,
+}
+
+impl ColumnReport {
+    pub fn new(columns: &[Vec<u32>]) -> Self {
+        let table = |metric: fn(Vec<u32>, Vec<u32>) -> u32| {
+            columns
+                .iter()
+                .map(|xs| {
+                    columns
+                        .iter()
+                        .map(|ys| metric(xs.clone(), ys.clone()))
+                        .collect()
+                })
+                .collect()
+        };
+        Self {
+            distance: table(distance),
+            similarity: table(similarity),
+        }
+    }
+}
+
+/// Reads columns of location IDs, returning each column as a list.  Every
+/// line needs as many columns as the first.  Columns may be separated by
+/// any run of spaces, tabs or commas, and blank lines are skipped.
+pub fn parse_columns(input: &str) -> Result<Vec<Vec<u32>>, AdventError> {
+    parse_rows(input, None)
+}
+
+/// Reads the two columns of location IDs, as `parse_columns` does.
+pub fn parse_two_columns(input: &str) -> Result<(Vec<u32>, Vec<u32>), AdventError> {
+    let mut columns = parse_rows(input, Some(2))?.into_iter();
+    let lhs = columns.next().unwrap_or_default();
+    let rhs = columns.next().unwrap_or_default();
+    Ok((lhs, rhs))
+}
+
+/// Reads the lines of numbers into columns, all of them `width` wide, or
+/// as wide as the first line if `width` isn't given.
+fn parse_rows(input: &str, mut width: Option<usize>) -> Result<Vec<Vec<u32>>, AdventError> {
+    let mut columns: Vec<Vec<u32>> = Vec::new();
+    for (lineno, line) in input.lines().enumerate() {
+        let fields: Vec<&str> = line
+            .split(|ch: char| ch.is_whitespace() || ch == ',')
+            .filter(|field| !field.is_empty())
+            .collect();
+        if fields.is_empty() {
+            continue;
+        }
+        let width = *width.get_or_insert(fields.len());
+        if fields.len() != width {
+            return Err(AdventError::parse(format!(
+                "Line {}: expected {} columns, found {:?}",
+                lineno + 1,
+                width,
+                line
+            )));
+        }
+        columns.resize_with(width, Vec::new);
+        for (column, field) in columns.iter_mut().zip(fields) {
+            column.push(field.parse::<u32>().map_err(|err| {
+                AdventError::parse(format!("Line {}: {:?}: {}", lineno + 1, field, err))
+            })?);
+        }
+    }
+    Ok(columns)
+}
+
+pub struct Day01;
+
+impl Solver for Day01 {
+    fn title(&self) -> &'static str {
+        "Historian Hysteria"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Sort both lists and pair them up; count occurrences for the similarity score."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        let (lhs, rhs) = parse_two_columns(input)?;
+        Ok(distance(lhs, rhs).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        let (lhs, rhs) = parse_two_columns(input)?;
+        Ok(similarity(lhs, rhs).to_string())
+    }
+}
@@ -0,0 +1,252 @@
+use crate::variants::Variant;
+use crate::{AdventError, Pairwise, Solver};
+use std::fmt::Write;
+use std::io::BufRead;
+use std::num::ParseIntError;
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    fn title(&self) -> &'static str {
+        "Red-Nosed Reports"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Check adjacent pairs; for part 2, find the longest safe run that skips at most one level."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        Ok(tally(input.as_bytes(), 1)?.safe.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        Ok(tally(input.as_bytes(), 1)?.tolerant.to_string())
+    }
+}
+
+/// Counts of the reports read so far.
+#[derive(Debug, Default, PartialEq)]
+pub struct Tally {
+    pub reports: usize,
+    /// Reports that are safe as they are.
+    pub safe: usize,
+    /// Reports that are safe once at most `k` levels are removed.
+    pub tolerant: usize,
+}
+
+/// Tallies the reports a line at a time as they're read, so only one
+/// report is ever held in memory, however large the input.
+pub fn tally(reader: impl BufRead, k: usize) -> Result<Tally, AdventError> {
+    let mut tally = Tally::default();
+    for line in reader.lines() {
+        let row = parse_report(&line?)?;
+        tally.reports += 1;
+        tally.safe += is_safe(&row) as usize;
+        tally.tolerant += is_safe_within(&row, k) as usize;
+    }
+    Ok(tally)
+}
+
+/// How many reports are safe once at most `k` levels are removed.
+pub fn count_tolerant(input: &str, k: usize) -> Result<usize, AdventError> {
+    Ok(tally(input.as_bytes(), k)?.tolerant)
+}
+
+fn is_safe(row: &[u32]) -> bool {
+    (all_pairwise(row, |x, y| x > y) || all_pairwise(row, |x, y| x < y))
+        && all_pairwise(row, |x, y| {
+            let diff = x.abs_diff(y);
+            (1..=3).contains(&diff)
+        })
+}
+
+/// The index of the first level whose removal makes the report safe, if
+/// any.  A report that's already safe stays safe without its first level.
+fn is_almost_safe(row: &[u32]) -> Option<usize> {
+    (0..row.len()).find(|&i| {
+        let mut modified = Vec::from(row);
+        modified.remove(i);
+        is_safe(&modified)
+    })
+}
+
+/// Whether removing at most `k` levels makes the report safe.  Rather than
+/// trying every set of removals, this finds, for each level, the fewest
+/// removals that leave a safe run ending there; a kept level can only
+/// follow one of the `k + 1` before it, so this takes O(nk) checks per
+/// direction.
+fn is_safe_within(row: &[u32], k: usize) -> bool {
+    if row.len() <= k + 1 {
+        return true;
+    }
+    let fewest_removals = |step_ok: fn(u32, u32) -> bool| {
+        let mut removals: Vec<usize> = Vec::with_capacity(row.len());
+        for (i, &level) in row.iter().enumerate() {
+            // Either this level starts the run, with every level before it
+            // removed, or it follows a nearby kept level.
+            let best = (i.saturating_sub(k + 1)..i)
+                .filter(|&j| step_ok(row[j], level))
+                .map(|j| removals[j] + (i - j - 1))
+                .fold(i, usize::min);
+            removals.push(best);
+        }
+        removals
+            .iter()
+            .enumerate()
+            .map(|(i, &r)| r + (row.len() - 1 - i))
+            .min()
+            .unwrap_or(0)
+    };
+    fewest_removals(|x, y| y > x && y - x <= 3) <= k
+        || fewest_removals(|x, y| x > y && x - y <= 3) <= k
+}
+
+/// A line per report saying whether it's safe, and if it takes the
+/// Problem Dampener, which level it drops.
+pub fn diagnostics(input: &str) -> Result<String, AdventError> {
+    let mut out = String::new();
+    for (lineno, row) in parse(input)?.iter().enumerate() {
+        let verdict = if is_safe(row) {
+            "safe".to_string()
+        } else if let Some(i) = is_almost_safe(row) {
+            format!("safe without level {} ({})", i + 1, row[i])
+        } else {
+            "unsafe".to_string()
+        };
+        let _ = writeln!(out, "Report {}: {:?}: {}", lineno + 1, row, verdict);
+    }
+    Ok(out)
+}
+
+fn all_pairwise(row: &[u32], test: impl Fn(u32, u32) -> bool) -> bool {
+    row.iter().pairwise().all(|(v1, v2)| test(*v1, *v2))
+}
+
+fn parse(content: &str) -> Result<Vec<Vec<u32>>, ParseIntError> {
+    content.lines().map(parse_report).collect()
+}
+
+fn parse_report(line: &str) -> Result<Vec<u32>, ParseIntError> {
+    line.split_whitespace().map(str::parse::<u32>).collect()
+}
+
+pub const PART2_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "tolerance",
+        run: |input| Ok(count_tolerant(input, 1)?.to_string()),
+    },
+    Variant {
+        name: "retry removals",
+        run: |input| {
+            Ok(parse(input)?
+                .iter()
+                .filter(|&v| is_safe(v) || is_almost_safe(v).is_some())
+                .count()
+                .to_string())
+        },
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinations;
+    use googletest::prelude::*;
+
+    const TEST_DATA: &str = crate::fixtures::DAY02.input;
+
+    #[gtest]
+    fn test_parsing() -> Result<()> {
+        verify_that!(
+            parse(TEST_DATA)?,
+            eq(&vec![
+                vec![7, 6, 4, 2, 1],
+                vec![1, 2, 7, 8, 9],
+                vec![9, 7, 6, 2, 1],
+                vec![1, 3, 2, 4, 5],
+                vec![8, 6, 4, 4, 1],
+                vec![1, 3, 6, 7, 9],
+            ])
+        )
+    }
+
+    #[gtest]
+    fn test_tally() -> Result<()> {
+        verify_that!(
+            tally(TEST_DATA.as_bytes(), 1)?,
+            eq(&Tally {
+                reports: 6,
+                safe: 2,
+                tolerant: 4,
+            })
+        )?;
+        verify_that!(tally(&b"1 2\n1 x\n"[..], 1), err(anything()))
+    }
+
+    #[gtest]
+    fn test_is_almost_safe() -> Result<()> {
+        verify_that!(is_almost_safe(&[7, 6, 4, 2, 1]), some(eq(0)))?;
+        verify_that!(is_almost_safe(&[1, 2, 7, 8, 9]), none())?;
+        verify_that!(is_almost_safe(&[1, 3, 2, 4, 5]), some(eq(1)))?;
+        verify_that!(is_almost_safe(&[8, 6, 4, 4, 1]), some(eq(2)))?;
+        verify_that!(is_almost_safe(&[9, 1, 2, 3]), some(eq(0)))
+    }
+
+    /// Whether some way of removing at most `k` levels leaves a safe report.
+    fn brute_force_safe_within(row: &[u32], k: usize) -> bool {
+        let indices: Vec<usize> = (0..row.len()).collect();
+        (0..=k.min(row.len())).any(|removed| {
+            combinations(&indices, removed).any(|gone| {
+                let kept: Vec<u32> = (0..row.len())
+                    .filter(|i| !gone.contains(&i))
+                    .map(|i| row[i])
+                    .collect();
+                is_safe(&kept)
+            })
+        })
+    }
+
+    #[gtest]
+    fn test_is_safe_within() -> Result<()> {
+        let rows = parse(TEST_DATA)?;
+        let safe: Vec<bool> = rows.iter().map(|row| is_safe_within(row, 0)).collect();
+        verify_that!(safe, eq(&vec![true, false, false, false, false, true]))?;
+        let safe: Vec<bool> = rows.iter().map(|row| is_safe_within(row, 1)).collect();
+        verify_that!(safe, eq(&vec![true, false, false, true, true, true]))?;
+        verify_that!(is_safe_within(&[1, 2, 7, 8, 9], 2), eq(true))?;
+        verify_that!(is_safe_within(&[1, 5, 9, 13, 17], 2), eq(false))?;
+        verify_that!(is_safe_within(&[1, 9, 2, 9, 3], 2), eq(true))?;
+        verify_that!(is_safe_within(&[9, 1, 6], 1), eq(true))?;
+        verify_that!(is_safe_within(&[], 0), eq(true))
+    }
+
+    #[gtest]
+    fn test_is_safe_within_agrees_with_brute_force() -> Result<()> {
+        // Every report of five levels from 1 to 6, with up to two removed.
+        for n in 0..6u32.pow(5) {
+            let row: Vec<u32> = (0..5).map(|i| n / 6u32.pow(i) % 6 + 1).collect();
+            for k in 0..3 {
+                verify_that!(
+                    (k, is_safe_within(&row, k)),
+                    eq((k, brute_force_safe_within(&row, k)))
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_diagnostics() -> Result<()> {
+        verify_that!(
+            diagnostics(TEST_DATA)?,
+            eq("\
+Report 1: [7, 6, 4, 2, 1]: safe
+Report 2: [1, 2, 7, 8, 9]: unsafe
+Report 3: [9, 7, 6, 2, 1]: unsafe
+Report 4: [1, 3, 2, 4, 5]: safe without level 2 (3)
+Report 5: [8, 6, 4, 4, 1]: safe without level 3 (4)
+Report 6: [1, 3, 6, 7, 9]: safe
+")
+        )
+    }
+}
@@ -0,0 +1,474 @@
+use crate::variants::Variant;
+use crate::{AdventError, Solver};
+use regex::bytes::Regex;
+use std::fmt::Write;
+use std::io::{self, Read};
+use std::ops::Range;
+
+#[derive(Debug)]
+struct Parser {
+    pattern: Regex,
+}
+
+#[derive(Debug, PartialEq)]
+enum Instruction {
+    Do,
+    Dont,
+    Mul { lhs: i32, rhs: i32 },
+}
+
+impl Instruction {
+    fn is_mul(&self) -> bool {
+        matches!(self, Instruction::Mul { .. })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct State {
+    enabled: bool,
+    /// Wide enough that no input of any realistic size can overflow it,
+    /// as each product is under 2^62.
+    val: i128,
+}
+
+impl State {
+    fn new() -> Self {
+        State {
+            enabled: true,
+            val: 0,
+        }
+    }
+    fn eval(&mut self, inst: &Instruction) {
+        match inst {
+            Instruction::Do => {
+                self.enabled = true;
+            }
+            Instruction::Dont => {
+                self.enabled = false;
+            }
+            Instruction::Mul { lhs, rhs } => {
+                if self.enabled {
+                    self.val += i128::from(*lhs) * i128::from(*rhs);
+                }
+            }
+        }
+    }
+}
+
+impl Parser {
+    fn new() -> Self {
+        let pattern = Regex::new(r"mul\((\d+),(\d+)\)|do\(\)|don't\(\)").unwrap();
+        Parser { pattern }
+    }
+
+    fn parse(&self, s: &str) -> Vec<Instruction> {
+        self.scan(s.as_bytes())
+            .filter_map(|(_, instruction)| instruction)
+            .collect()
+    }
+
+    /// Every match in `bytes`, with where it was, and the instruction it
+    /// spells, or None if its numbers don't fit an `i32`.
+    fn scan<'a>(
+        &'a self,
+        bytes: &'a [u8],
+    ) -> impl Iterator<Item = (Range<usize>, Option<Instruction>)> + 'a {
+        self.pattern.captures_iter(bytes).map(|captures| {
+            let whole = captures.get(0).unwrap();
+            let instruction = match whole.as_bytes() {
+                b"do()" => Some(Instruction::Do),
+                b"don't()" => Some(Instruction::Dont),
+                _ => parse_number(&captures[1])
+                    .zip(parse_number(&captures[2]))
+                    .map(|(lhs, rhs)| Instruction::Mul { lhs, rhs }),
+            };
+            (whole.range(), instruction)
+        })
+    }
+}
+
+fn parse_number(digits: &[u8]) -> Option<i32> {
+    std::str::from_utf8(digits).ok()?.parse().ok()
+}
+
+/// The most digits a number can have and still fit an `i32`, not counting
+/// leading zeros.
+const MAX_DIGITS: usize = 10;
+
+fn without_leading_zeros(digits: &[u8]) -> &[u8] {
+    let zeros = digits.iter().take_while(|&&byte| byte == b'0').count();
+    &digits[zeros..]
+}
+
+/// Where the end of `buffer` starts spelling an instruction that more
+/// bytes might finish, or the buffer's length if it doesn't.  Leading
+/// zeros mean a `mul` that still fits an `i32` can be any length, so this
+/// looks at what the bytes say rather than how many there are.  Only an
+/// `m` or a `d` can start one, and no instruction has either inside it, so
+/// it can only start at the last of each.
+fn unfinished_start(buffer: &[u8]) -> usize {
+    [b'm', b'd']
+        .into_iter()
+        .filter_map(|first| buffer.iter().rposition(|&byte| byte == first))
+        .filter(|&start| could_continue(&buffer[start..]))
+        .min()
+        .unwrap_or(buffer.len())
+}
+
+/// Whether `tail` is the start of an instruction, but not all of one.
+fn could_continue(tail: &[u8]) -> bool {
+    if let Some(args) = tail.strip_prefix(b"mul(") {
+        return args.splitn(2, |&byte| byte == b',').all(|digits| {
+            digits.iter().all(u8::is_ascii_digit)
+                && without_leading_zeros(digits).len() <= MAX_DIGITS
+        });
+    }
+    [&b"mul("[..], b"do()", b"don't()"]
+        .into_iter()
+        .any(|literal| tail.len() < literal.len() && literal.starts_with(tail))
+}
+
+/// Shortens each run of leading zeros in an unfinished `mul` to a single
+/// zero, which reads as the same number, so however many zeros the input
+/// has, what's held back stays no longer than the longest instruction.
+fn squeeze_leading_zeros(tail: &mut Vec<u8>) {
+    let Some(args) = tail.strip_prefix(b"mul(") else {
+        return;
+    };
+    let mut squeezed = b"mul(".to_vec();
+    for (i, digits) in args.split(|&byte| byte == b',').enumerate() {
+        if i > 0 {
+            squeezed.push(b',');
+        }
+        let significant = without_leading_zeros(digits);
+        if significant.len() < digits.len() {
+            squeezed.push(b'0');
+        }
+        squeezed.extend_from_slice(significant);
+    }
+    *tail = squeezed;
+}
+
+/// Runs the instructions straight from `reader`, without reading it all
+/// into memory first, and returns the totals for parts 1 and 2.
+pub fn evaluate_stream(reader: impl Read) -> Result<(i128, i128), AdventError> {
+    Ok(evaluate_chunked(reader, 64 * 1024)?)
+}
+
+/// Reads `chunk_size` bytes at a time.  The end of the buffer may be the
+/// start of an instruction whose rest hasn't been read yet, so from where
+/// that starts, the bytes wait for the next chunk.
+fn evaluate_chunked(mut reader: impl Read, chunk_size: usize) -> io::Result<(i128, i128)> {
+    let parser = Parser::new();
+    let mut all = State::new();
+    let mut gated = State::new();
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut chunk = vec![0; chunk_size];
+    loop {
+        let n = match reader.read(&mut chunk) {
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            result => result?,
+        };
+        buffer.extend_from_slice(&chunk[..n]);
+        let at_end = n == 0;
+        let cutoff = if at_end {
+            buffer.len()
+        } else {
+            unfinished_start(&buffer)
+        };
+
+        let mut resume = cutoff;
+        for (range, instruction) in parser.scan(&buffer) {
+            if range.start >= cutoff {
+                break;
+            }
+            resume = resume.max(range.end);
+            if let Some(instruction) = instruction {
+                if instruction.is_mul() {
+                    all.eval(&instruction);
+                }
+                gated.eval(&instruction);
+            }
+        }
+        if at_end {
+            return Ok((all.val, gated.val));
+        }
+        buffer.drain(..resume);
+        squeeze_leading_zeros(&mut buffer);
+    }
+}
+
+/// The same instructions found by parser combinators instead of one
+/// regex, each with the bytes of the input that spelled it.
+mod parser {
+    use super::*;
+    use nom::branch::alt;
+    use nom::bytes::complete::tag;
+    use nom::character::complete::digit1;
+    use nom::combinator::{map, map_res};
+    use nom::sequence::{delimited, separated_pair};
+    use nom::IResult;
+
+    /// An instruction and where in the input it was.
+    #[derive(Debug, PartialEq)]
+    pub struct Spanned {
+        pub span: Range<usize>,
+        pub instruction: Instruction,
+    }
+
+    fn number(input: &str) -> IResult<&str, i32> {
+        map_res(digit1, str::parse)(input)
+    }
+
+    fn mul(input: &str) -> IResult<&str, Instruction> {
+        map(
+            delimited(
+                tag("mul("),
+                separated_pair(number, tag(","), number),
+                tag(")"),
+            ),
+            |(lhs, rhs)| Instruction::Mul { lhs, rhs },
+        )(input)
+    }
+
+    fn instruction(input: &str) -> IResult<&str, Instruction> {
+        alt((
+            mul,
+            map(tag("do()"), |_| Instruction::Do),
+            map(tag("don't()"), |_| Instruction::Dont),
+        ))(input)
+    }
+
+    /// Tries for an instruction at each position in turn, skipping a
+    /// character whenever there isn't one.
+    pub fn parse_spanned(input: &str) -> Vec<Spanned> {
+        let mut result = Vec::new();
+        let mut rest = input;
+        while let Some(ch) = rest.chars().next() {
+            let start = input.len() - rest.len();
+            match instruction(rest) {
+                Ok((after, instruction)) => {
+                    let end = input.len() - after.len();
+                    result.push(Spanned {
+                        span: start..end,
+                        instruction,
+                    });
+                    rest = after;
+                }
+                Err(_) => rest = &rest[ch.len_utf8()..],
+            }
+        }
+        result
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use googletest::prelude::*;
+
+        #[gtest]
+        fn test_parse_spanned() -> Result<()> {
+            let input = crate::fixtures::DAY03.input;
+            let spanned = parse_spanned(input);
+            let spans: Vec<&str> = spanned.iter().map(|s| &input[s.span.clone()]).collect();
+            verify_that!(
+                spans,
+                eq(&vec![
+                    "mul(2,4)",
+                    "don't()",
+                    "mul(5,5)",
+                    "mul(11,8)",
+                    "do()",
+                    "mul(8,5)"
+                ])
+            )?;
+            verify_that!(
+                spanned.iter().map(|s| &s.instruction).collect::<Vec<_>>(),
+                eq(&Parser::new().parse(input).iter().collect::<Vec<_>>())
+            )
+        }
+
+        #[gtest]
+        fn test_parse_spanned_skips_what_regex_skips() -> Result<()> {
+            let input = "mul(12345678901234,5)mul(-1,2)mul(3,4)émul(1,1)";
+            verify_that!(
+                parse_spanned(input)
+                    .into_iter()
+                    .map(|s| s.instruction)
+                    .collect::<Vec<_>>(),
+                eq(&Parser::new().parse(input))
+            )
+        }
+    }
+}
+
+/// A line per instruction, as part 2 runs them: the bytes it came from,
+/// its text, and what it did.
+pub fn explanation(input: &str) -> Result<String, AdventError> {
+    let mut out = String::new();
+    let mut state = State::new();
+    for parser::Spanned { span, instruction } in parser::parse_spanned(input) {
+        let effect = match instruction {
+            Instruction::Do => "enable".to_string(),
+            Instruction::Dont => "disable".to_string(),
+            Instruction::Mul { lhs, rhs } if state.enabled => {
+                format!("add {}", i128::from(lhs) * i128::from(rhs))
+            }
+            Instruction::Mul { .. } => "skip".to_string(),
+        };
+        let _ = writeln!(
+            out,
+            "{}..{} {}: {}",
+            span.start,
+            span.end,
+            &input[span.clone()],
+            effect
+        );
+        state.eval(&instruction);
+    }
+    let _ = writeln!(out, "Total: {}", state.val);
+    Ok(out)
+}
+
+fn sum_spanned(input: &str, gated: bool) -> i128 {
+    let mut state = State::new();
+    for parser::Spanned { instruction, .. } in parser::parse_spanned(input) {
+        if gated || instruction.is_mul() {
+            state.eval(&instruction);
+        }
+    }
+    state.val
+}
+
+pub const PART1_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "regex",
+        run: |input| Day03.part1(input),
+    },
+    Variant {
+        name: "nom",
+        run: |input| Ok(sum_spanned(input, false).to_string()),
+    },
+];
+
+pub const PART2_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "regex",
+        run: |input| Day03.part2(input),
+    },
+    Variant {
+        name: "nom",
+        run: |input| Ok(sum_spanned(input, true).to_string()),
+    },
+];
+
+pub struct Day03;
+
+impl Solver for Day03 {
+    fn title(&self) -> &'static str {
+        "Mull It Over"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Regex over the instructions, fed through a small enable/disable state machine."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        let mut state = State::new();
+        for instruction in Parser::new().parse(input).iter().filter(|&x| x.is_mul()) {
+            state.eval(instruction);
+        }
+        Ok(state.val.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        let mut state = State::new();
+        for instruction in Parser::new().parse(input).iter() {
+            state.eval(instruction)
+        }
+        Ok(state.val.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use indoc::indoc;
+
+    #[gtest]
+    fn test_parse() -> Result<()> {
+        let parser = Parser::new();
+        let instructions =
+            parser.parse("xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))");
+        verify_that!(
+            instructions,
+            eq(&vec![
+                Instruction::Mul { lhs: 2, rhs: 4 },
+                Instruction::Mul { lhs: 5, rhs: 5 },
+                Instruction::Mul { lhs: 11, rhs: 8 },
+                Instruction::Mul { lhs: 8, rhs: 5 }
+            ])
+        )
+    }
+
+    #[gtest]
+    fn test_evaluate_chunked() -> Result<()> {
+        let input = crate::fixtures::DAY03.input.repeat(3)
+            + "mul(12345678901234,5)mul(1234567,1)don't()mul(1,2)do()mul(3,4)"
+            + "mul(2,3)mul(00000000000000000000000000007,3)";
+        let expected = (
+            Day03.part1(&input)?.parse::<i128>()?,
+            Day03.part2(&input)?.parse::<i128>()?,
+        );
+        for chunk_size in 1..=48 {
+            verify_that!(
+                (chunk_size, evaluate_chunked(input.as_bytes(), chunk_size)?),
+                eq((chunk_size, expected))
+            )?;
+        }
+        verify_that!(evaluate_stream(input.as_bytes())?, eq(expected))
+    }
+
+    #[gtest]
+    fn test_evaluate_stream_large() -> Result<()> {
+        // Far past where an i32 total overflows.
+        let input = "mul(2147483647,2147483647)don't()do()".repeat(100_000);
+        let total = 100_000 * 2147483647i128 * 2147483647;
+        verify_that!(evaluate_stream(input.as_bytes())?, eq((total, total)))?;
+        verify_that!(Day03.part1(&input)?, eq(&total.to_string()))?;
+        // However many leading zeros, a number still reads the same.
+        let input = format!("mul(2,{}3)", "0".repeat(1_000_000));
+        verify_that!(evaluate_chunked(input.as_bytes(), 4096)?, eq((6, 6)))
+    }
+
+    #[gtest]
+    fn test_held_back_tail_is_bounded() -> Result<()> {
+        let mut tail = format!("mul({}12,{}", "0".repeat(1000), "0".repeat(1000)).into_bytes();
+        squeeze_leading_zeros(&mut tail);
+        verify_that!(tail, eq(&b"mul(012,0".to_vec()))?;
+        // Too many digits to fit an i32 can't be finished into a match.
+        let digits = "1".repeat(MAX_DIGITS + 1);
+        verify_that!(
+            could_continue(format!("mul({}", digits).as_bytes()),
+            is_false()
+        )?;
+        verify_that!(could_continue(b"mul(0000000000001,2"), is_true())
+    }
+
+    #[gtest]
+    fn test_explanation() -> Result<()> {
+        verify_that!(
+            explanation(crate::fixtures::DAY03.input)?,
+            eq(indoc! {"
+                1..9 mul(2,4): add 8
+                20..27 don't(): disable
+                28..36 mul(5,5): skip
+                48..57 mul(11,8): skip
+                59..63 do(): enable
+                64..72 mul(8,5): add 40
+                Total: 48
+            "})
+        )
+    }
+}
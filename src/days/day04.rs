@@ -0,0 +1,180 @@
+use crate::{AdventError, Direction8, Solver, Stencil, WordSearch};
+
+fn count_xmas(search: &WordSearch) -> usize {
+    search.count_word("XMAS")
+}
+
+/// Counts the MAS crosses.  The four ways to write one:
+///
+/// ```text
+/// M.S    S.M    S.S    M.M
+/// .A.    .A.    .A.    .A.
+/// M.S    S.M    M.M    S.S
+/// ```
+///
+/// are quarter turns of each other.
+fn count_xmas2(search: &WordSearch) -> Result<usize, String> {
+    Ok(search.count_stencils(&crosses()?))
+}
+
+fn crosses() -> Result<Vec<Stencil>, String> {
+    Ok(Stencil::parse("M.S\n.A.\nM.S")?.rotations())
+}
+
+/// Where XMAS reads: the row and column of each X, and which way it reads
+/// from there.
+pub fn xmas_matches(search: &WordSearch) -> Vec<(usize, usize, Direction8)> {
+    search
+        .find_word("XMAS")
+        .map(|(index, dir)| {
+            let (row, col) = search.letters().tiles.to_rc(index);
+            (row, col, dir)
+        })
+        .collect()
+}
+
+/// The row and column of the A in the middle of each X-MAS, row by row.
+pub fn xmas_centers(search: &WordSearch) -> Result<Vec<(usize, usize)>, String> {
+    let mut centers: Vec<(usize, usize)> = crosses()?
+        .iter()
+        .flat_map(|cross| search.find_stencil(cross))
+        .map(|index| {
+            let (row, col) = search.letters().tiles.to_rc(index);
+            (row + 1, col + 1)
+        })
+        .collect();
+    centers.sort();
+    Ok(centers)
+}
+
+/// The grid with only the letters of each part's matches showing, as the
+/// puzzle draws them.
+pub fn explanation(input: &str) -> Result<String, AdventError> {
+    let search = WordSearch::parse(input)?;
+    let xmas = search
+        .find_word("XMAS")
+        .flat_map(|(index, dir)| search.word_tiles("XMAS", index, dir));
+    let part1 = search.overlay(xmas);
+    let crosses = crosses()?;
+    let x_mas = crosses.iter().flat_map(|cross| {
+        search
+            .find_stencil(cross)
+            .flat_map(|index| search.stencil_tiles(cross, index))
+    });
+    let part2 = search.overlay(x_mas);
+    Ok(format!("XMAS:\n{}\nX-MAS:\n{}", part1, part2))
+}
+
+pub struct Day04;
+
+impl Solver for Day04 {
+    fn title(&self) -> &'static str {
+        "Ceres Search"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Read XMAS from every cell in each of eight directions; part 2 matches each quarter turn of the X-MAS cross."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        Ok(count_xmas(&WordSearch::parse(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        Ok(count_xmas2(&WordSearch::parse(input)?)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use indoc::indoc;
+
+    const S: &str = crate::fixtures::DAY04.input;
+
+    #[test]
+    fn test_example() -> Result<()> {
+        let search = WordSearch::parse(S).into_test_result()?;
+        verify_that!(count_xmas(&search), eq(18))
+    }
+
+    #[test]
+    fn test_matches_xmas2() -> Result<()> {
+        let search = WordSearch::parse(S).into_test_result()?;
+        verify_that!(count_xmas2(&search).into_test_result()?, eq(9))
+    }
+
+    #[gtest]
+    fn test_xmas_positions() -> Result<()> {
+        let search = WordSearch::parse(S).into_test_result()?;
+        let matches = xmas_matches(&search);
+        verify_that!(matches.len(), eq(18))?;
+        verify_that!(matches[0], eq((0, 4, Direction8::DownRight)))?;
+        let centers = xmas_centers(&search).into_test_result()?;
+        verify_that!(
+            centers,
+            eq(&vec![
+                (1, 2),
+                (2, 6),
+                (2, 7),
+                (3, 2),
+                (3, 4),
+                (7, 1),
+                (7, 3),
+                (7, 5),
+                (7, 7)
+            ])
+        )
+    }
+
+    #[gtest]
+    fn test_explanation() -> Result<()> {
+        verify_that!(
+            explanation(S)?,
+            eq(indoc! {"
+                XMAS:
+                ....XXMAS.
+                .SAMXMS...
+                ...S..A...
+                ..A.A.MS.X
+                XMASAMX.MM
+                X.....XA.A
+                S.S.S.S.SS
+                .A.A.A.A.A
+                ..M.M.M.MM
+                .X.X.XMASX
+
+                X-MAS:
+                .M.S......
+                ..A..MSMS.
+                .M.S.MAA..
+                ..A.ASMSM.
+                .M.S.M....
+                ..........
+                S.S.S.S.S.
+                .A.A.A.A..
+                M.M.M.M.M.
+                ..........
+            "})
+        )
+    }
+
+    #[gtest]
+    fn test_matches_xmas2_orientations() -> Result<()> {
+        for (pattern, expected) in [
+            ("M.S\n.A.\nM.S", 1),
+            ("S.M\n.A.\nS.M", 1),
+            ("S.S\n.A.\nM.M", 1),
+            ("M.M\n.A.\nS.S", 1),
+            ("M.S\n.A.\nS.M", 0),
+            ("MAS\nAAA\nSAM", 0),
+            ("M.S\n.X.\nM.S", 0),
+        ] {
+            let search = WordSearch::parse(pattern).into_test_result()?;
+            let count = count_xmas2(&search).into_test_result()?;
+            verify_that!((pattern, count), eq((pattern, expected)))?;
+        }
+        Ok(())
+    }
+}
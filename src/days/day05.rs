@@ -0,0 +1,373 @@
+use crate::graph::TopologicalSort;
+use crate::variants::Variant;
+use crate::{split_sections, AdventError, FromInput, FxHashMap, FxHashSet, Solver};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, PartialEq)]
+struct Problem {
+    orderings: Vec<(u32, u32)>,
+    numbers: Vec<Vec<u32>>,
+}
+
+impl FromInput for Problem {
+    fn from_input(input: &str) -> Result<Self, AdventError> {
+        let [orderings, numbers] = split_sections(input, ["ordering", "numbers"])?;
+        let orderings = orderings
+            .lines()
+            .map(|line| {
+                line.split("|")
+                    .map(str::parse::<u32>)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(AdventError::from)
+                    .and_then(|numbers| {
+                        numbers
+                            .first()
+                            .ok_or(AdventError::parse(format!("lhs missing from {:?}", line)))
+                            .and_then(|&n1| {
+                                numbers
+                                    .get(1)
+                                    .ok_or(AdventError::parse(format!(
+                                        "rhs missing from {:?}",
+                                        line
+                                    )))
+                                    .map(|&n2| (n1, n2))
+                            })
+                    })
+            })
+            .collect::<Result<Vec<_>, AdventError>>()?;
+
+        let numbers = numbers
+            .lines()
+            .map(|line| {
+                line.split(",")
+                    .map(str::parse::<u32>)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(AdventError::from)
+            })
+            .collect::<Result<Vec<_>, AdventError>>()?;
+        Ok(Problem { orderings, numbers })
+    }
+}
+
+/// The rules, indexed by the page that must come first, built once for
+/// all the updates.
+struct Rules {
+    children_of: FxHashMap<u32, FxHashSet<u32>>,
+}
+
+impl Rules {
+    fn new(orderings: &[(u32, u32)]) -> Self {
+        let mut children_of: FxHashMap<u32, FxHashSet<u32>> = FxHashMap::default();
+        for &(parent, child) in orderings {
+            children_of.entry(parent).or_default().insert(child);
+        }
+        Self { children_of }
+    }
+
+    fn children(&self, page: u32) -> impl Iterator<Item = u32> + '_ {
+        self.children_of.get(&page).into_iter().flatten().copied()
+    }
+
+    /// Whether no page in `numbers` comes after a page that must follow it.
+    fn allows(&self, numbers: &[u32]) -> bool {
+        let mut seen: FxHashSet<u32> = FxHashSet::default();
+        numbers.iter().all(|&n| {
+            let ok = self.children(n).all(|child| !seen.contains(&child));
+            seen.insert(n);
+            ok
+        })
+    }
+
+    /// The rules between pages of `numbers`, as `(parent, child)`.
+    fn applicable(&self, numbers: &[u32]) -> Vec<(u32, u32)> {
+        let pages: FxHashSet<u32> = numbers.iter().copied().collect();
+        numbers
+            .iter()
+            .flat_map(|&parent| {
+                self.children(parent)
+                    .filter(|child| pages.contains(child))
+                    .map(move |child| (parent, child))
+            })
+            .collect()
+    }
+}
+
+fn filter_correct_numbers(p: &Problem) -> Vec<&Vec<u32>> {
+    let rules = Rules::new(&p.orderings);
+    p.numbers
+        .iter()
+        .filter(|numbers| rules.allows(numbers))
+        .collect()
+}
+
+/// Like `filter_correct_numbers`, but collecting the rules that apply to
+/// each update afresh, for comparison.
+fn filter_correct_numbers_per_update(p: &Problem) -> Vec<&Vec<u32>> {
+    p.numbers
+        .iter()
+        .filter(|numbers| {
+            // Record the child->parent dependencies for quicker lookup.
+            let mut rules: HashMap<u32, HashSet<u32>> = HashMap::new();
+            {
+                let seen_in_numbers: HashSet<u32> = numbers.iter().copied().collect();
+                for (parent, child) in p.orderings.iter() {
+                    if seen_in_numbers.contains(parent) && seen_in_numbers.contains(child) {
+                        rules.entry(*child).or_default().insert(*parent);
+                    }
+                }
+            }
+
+            let mut seen: HashSet<u32> = HashSet::new();
+
+            for &n in numbers.iter() {
+                if rules
+                    .entry(n)
+                    .or_default()
+                    .iter()
+                    .any(|parent| !seen.contains(parent))
+                {
+                    return false;
+                }
+
+                seen.insert(n);
+            }
+
+            true
+        })
+        .collect()
+}
+
+/// Reorders each incorrect update by the rules that apply to it.  Rules
+/// that go round in a circle can't be satisfied, so they're an error
+/// naming the update and the pages in the circle.
+fn fix_broken_numbers(problem: &Problem) -> Result<Vec<Vec<u32>>, AdventError> {
+    let rules = Rules::new(&problem.orderings);
+    problem
+        .numbers
+        .iter()
+        .filter(|numbers| !rules.allows(numbers))
+        .map(|numbers| {
+            TopologicalSort::new(rules.applicable(numbers))
+                .with_nodes(numbers.iter().copied())
+                .stable()
+                .sorted()
+                .map_err(|cycle| {
+                    AdventError::no_solution(format!("Can't reorder {:?}: {}", numbers, cycle))
+                })
+        })
+        .collect()
+}
+
+fn middle(v: &[u32]) -> u32 {
+    v[v.len() / 2]
+}
+
+fn part1(p: &Problem) -> u32 {
+    filter_correct_numbers(p)
+        .into_iter()
+        .map(|numbers| middle(numbers))
+        .sum::<u32>()
+}
+
+fn part2(p: &Problem) -> Result<u32, AdventError> {
+    Ok(fix_broken_numbers(p)?
+        .into_iter()
+        .map(|numbers| middle(&numbers))
+        .sum::<u32>())
+}
+
+/// Part 2 sorting each update directly, with the rules as its comparison.
+/// That only works because the puzzle's rules order every pair of pages
+/// in an update, and consistently; the topological sort doesn't need
+/// that.  So each update is checked first: ordered that way, each page
+/// comes before a different number of the others, from none up.
+fn part2_by_comparator(p: &Problem) -> Result<u32, AdventError> {
+    let rules: FxHashSet<(u32, u32)> = p.orderings.iter().copied().collect();
+    let totally_ordered = |numbers: &[u32]| {
+        let mut befores: Vec<usize> = numbers
+            .iter()
+            .map(|a| {
+                numbers
+                    .iter()
+                    .filter(|b| rules.contains(&(*a, **b)))
+                    .count()
+            })
+            .collect();
+        befores.sort_unstable();
+        befores.into_iter().eq(0..numbers.len())
+    };
+    let compare = |a: &u32, b: &u32| {
+        if rules.contains(&(*a, *b)) {
+            Ordering::Less
+        } else if rules.contains(&(*b, *a)) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    };
+    let mut total = 0;
+    for numbers in &p.numbers {
+        if !totally_ordered(numbers) {
+            return Err(AdventError::no_solution(format!(
+                "The rules don't order every pair of {:?} consistently",
+                numbers
+            )));
+        }
+        let mut sorted = numbers.clone();
+        sorted.sort_by(compare);
+        if &sorted != numbers {
+            total += middle(&sorted);
+        }
+    }
+    Ok(total)
+}
+
+pub const PART1_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "indexed rules",
+        run: |input| Ok(part1(&Problem::from_input(input)?).to_string()),
+    },
+    Variant {
+        name: "rules per update",
+        run: |input| {
+            let problem = Problem::from_input(input)?;
+            let sum: u32 = filter_correct_numbers_per_update(&problem)
+                .into_iter()
+                .map(|numbers| middle(numbers))
+                .sum();
+            Ok(sum.to_string())
+        },
+    },
+];
+
+pub const PART2_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "topological sort",
+        run: |input| Ok(part2(&Problem::from_input(input)?)?.to_string()),
+    },
+    Variant {
+        name: "comparator",
+        run: |input| Ok(part2_by_comparator(&Problem::from_input(input)?)?.to_string()),
+    },
+];
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    fn title(&self) -> &'static str {
+        "Print Queue"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Check updates against the rules; fix bad ones with a topological sort of the applicable rules."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        Ok(part1(&Problem::from_input(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        Ok(part2(&Problem::from_input(input)?)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    const TEST_DATA: &str = crate::fixtures::DAY05.input;
+
+    #[gtest]
+    fn test_parse() -> Result<()> {
+        let problem = Problem::from_input(
+            "\
+	    45|53
+97|13
+
+75,47,61
+97,61,53",
+        );
+        verify_that!(
+            problem,
+            ok(eq(&Problem {
+                orderings: vec![(45, 53), (97, 13)],
+                numbers: vec![vec![75, 47, 61], vec![97, 61, 53],]
+            }))
+        )
+    }
+
+    #[gtest]
+    fn test_filtering() -> Result<()> {
+        let problem = Problem::from_input(TEST_DATA).unwrap();
+        verify_that!(
+            filter_correct_numbers(&problem),
+            container_eq(vec![
+                &vec![75, 47, 61, 53, 29],
+                &vec![97, 61, 53, 29, 13],
+                &vec![75, 29, 13],
+            ])
+        )
+    }
+
+    #[gtest]
+    fn test_filtering_per_update() -> Result<()> {
+        let problem = Problem::from_input(TEST_DATA)?;
+        verify_that!(
+            filter_correct_numbers_per_update(&problem),
+            eq(&filter_correct_numbers(&problem))
+        )
+    }
+
+    #[gtest]
+    fn test_fix_broken_numbers() -> Result<()> {
+        let problem = Problem::from_input(TEST_DATA).unwrap();
+        verify_that!(
+            fix_broken_numbers(&problem).into_test_result()?,
+            container_eq(vec![
+                vec![97, 75, 47, 61, 53],
+                vec![61, 29, 13],
+                vec![97, 75, 47, 29, 13],
+            ])
+        )?;
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_unruled_pages_are_kept() -> Result<()> {
+        // Nothing orders 7, but it's still on the page.
+        let problem = Problem::from_input("2|1\n\n1,2,7\n")?;
+        verify_that!(fix_broken_numbers(&problem)?, eq(&vec![vec![2, 1, 7]]))
+    }
+
+    #[gtest]
+    fn test_cyclic_rules() -> Result<()> {
+        let problem = Problem::from_input("1|2\n2|3\n3|1\n\n1,2\n3,2,1\n")?;
+        verify_that!(
+            part2(&problem).map_err(|e| e.to_string()),
+            err(starts_with(
+                "No solution: Can't reorder [3, 2, 1]: Ordering has a cycle:"
+            ))
+        )?;
+        // Every pair is ruled, but not consistently, so there's no sorting
+        // by them either.
+        verify_that!(
+            part2_by_comparator(&problem).map_err(|e| e.to_string()),
+            err(eq(
+                "No solution: The rules don't order every pair of [3, 2, 1] consistently"
+            ))
+        )?;
+        // Nor with a pair not ruled at all.
+        let problem = Problem::from_input("2|1\n\n1,2,7\n")?;
+        verify_that!(part2_by_comparator(&problem), err(anything()))
+    }
+
+    #[gtest]
+    fn test_parts() -> Result<()> {
+        let problem = Problem::from_input(TEST_DATA)?;
+        verify_that!(part1(&problem), eq(143))?;
+        verify_that!(part2(&problem).into_test_result()?, eq(123))?;
+        verify_that!(part2_by_comparator(&problem)?, eq(123))
+    }
+}
@@ -0,0 +1,428 @@
+use crate::variants::Variant;
+use crate::{
+    cycle_len, AdventError, Agent, BitGrid, Direction, DirectionSet, FromInput, FxHashSet, Grid,
+    Render, Solver,
+};
+use rayon::prelude::*;
+
+#[derive(Debug, PartialEq, Clone)]
+struct World {
+    player: Agent,
+    blocked: BitGrid,
+}
+
+impl FromInput for World {
+    fn from_input(input: &str) -> Result<Self, AdventError> {
+        let chars = Grid::parse_chars(input, |ch| ch)?;
+        let mut blocked = BitGrid::new(chars.tiles.clone());
+        let mut player = None;
+        for (index, &ch) in chars.iter_with_index() {
+            match ch {
+                '#' => blocked.set(index),
+                '.' => {}
+                _ => match Direction::try_from(ch) {
+                    Ok(dir) => player = Some(Agent { pos: index, dir }),
+                    Err(_) => log::warn!("Ignoring unknown map character {:?}", ch),
+                },
+            }
+        }
+        let player = player.ok_or_else(|| AdventError::parse("No guard on the map"))?;
+        Ok(World { player, blocked })
+    }
+}
+
+impl World {
+    fn steps(&self) -> Stepper<'_> {
+        Stepper {
+            blocked: &self.blocked,
+            player: self.player,
+            exhausted: false,
+        }
+    }
+
+    /// Every tick of the guard's walk, from where they start until the
+    /// last tile before they leave.  A turn shows up as two ticks on the
+    /// same tile.
+    fn trace(&self) -> Vec<Agent> {
+        self.steps().collect()
+    }
+}
+
+#[derive(Clone)]
+struct Stepper<'a> {
+    blocked: &'a BitGrid,
+    player: Agent,
+    exhausted: bool,
+}
+
+impl Stepper<'_> {
+    fn peek(&mut self) -> Option<Agent> {
+        if self.exhausted {
+            return None;
+        }
+        Some(self.player)
+    }
+
+    /// Where `player` is a tick later: a step forward, or a turn if a
+    /// block is in the way.  None once they walk off the map.
+    fn advance(&self, player: &Agent) -> Option<Agent> {
+        let ahead = player.forward(&self.blocked.tiles)?;
+        if self.blocked.contains(ahead.pos) {
+            Some(player.turn_clock())
+        } else {
+            Some(ahead)
+        }
+    }
+
+    /// Whether the guard walks in a loop, noting in `seen` which ways they've
+    /// faced on each tile.  Being on the same tile facing the same way twice
+    /// means they're going round.  `seen` is cleared first, so one can be
+    /// reused across many checks without allocating.
+    fn is_infinite_looping(&self, seen: &mut Vec<DirectionSet>) -> bool {
+        if self.exhausted {
+            return false;
+        }
+        seen.clear();
+        let tiles = &self.blocked.tiles;
+        seen.resize(tiles.width * tiles.height, DirectionSet::default());
+        let mut player = self.player;
+        loop {
+            if !seen[player.pos].insert(player.dir) {
+                return true;
+            }
+            match self.advance(&player) {
+                Some(next) => player = next,
+                None => return false,
+            }
+        }
+    }
+}
+
+impl Stepper<'_> {
+    /// Like `is_infinite_looping`, but with Brent's cycle detection, which
+    /// keeps only a couple of states rather than a direction set per tile,
+    /// in exchange for walking the path a few times over.
+    fn is_infinite_looping_brent(&self) -> bool {
+        // Having left the map is a state that steps to itself, while going
+        // round a loop takes at least four turns.
+        let start = (!self.exhausted).then_some(self.player);
+        cycle_len(start, |player| {
+            player.as_ref().and_then(|player| self.advance(player))
+        }) > 1
+    }
+}
+
+impl Iterator for Stepper<'_> {
+    type Item = Agent;
+
+    fn next(&mut self) -> Option<Agent> {
+        let result = self.peek()?;
+        match self.advance(&self.player) {
+            Some(player) => self.player = player,
+            None => self.exhausted = true,
+        }
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TileIndex;
+    use googletest::prelude::*;
+    use indoc::indoc;
+
+    const DATA: &str = crate::fixtures::DAY06.input;
+
+    #[gtest]
+    fn test_parsing() -> Result<()> {
+        let world = World::from_input(DATA)?;
+        let tiles = TileIndex {
+            width: 10,
+            height: 10,
+        };
+        let mut blocked = BitGrid::new(tiles.clone());
+        for (row, col) in [
+            (0, 4),
+            (1, 9),
+            (3, 2),
+            (4, 7),
+            (6, 1),
+            (7, 8),
+            (8, 0),
+            (9, 6),
+        ] {
+            blocked.set(tiles.from_rc(row, col));
+        }
+        verify_that!(
+            world,
+            eq(&World {
+                player: Agent {
+                    pos: tiles.from_rc(6, 4),
+                    dir: Direction::Up
+                },
+                blocked,
+            })
+        )
+    }
+
+    #[gtest]
+    fn test_stepping() -> Result<()> {
+        let world = World::from_input(DATA)?;
+        let tiles = &world.blocked.tiles;
+        let positions: Vec<(usize, usize)> = world
+            .steps()
+            .take(9)
+            .map(|player| tiles.to_rc(player.pos))
+            .collect();
+        verify_that!(
+            positions,
+            eq(&vec![
+                (6, 4),
+                (5, 4),
+                (4, 4),
+                (3, 4),
+                (2, 4),
+                (1, 4),
+                (1, 4),
+                (1, 5),
+                (1, 6)
+            ])
+        )
+    }
+
+    #[gtest]
+    fn test_distinct_pathing() -> Result<()> {
+        let world = World::from_input(DATA)?;
+        let steps = world.steps();
+        let posn: FxHashSet<_> = steps.map(|player| player.pos).collect();
+        verify_that!(posn.len(), eq(41))
+    }
+
+    #[gtest]
+    fn test_infinite_looping_negative() -> Result<()> {
+        let world = World::from_input(DATA)?;
+        verify_that!(
+            world.steps().is_infinite_looping(&mut Vec::new()),
+            is_false()
+        )?;
+        verify_that!(world.steps().is_infinite_looping_brent(), is_false())
+    }
+
+    #[gtest]
+    fn test_infinite_looping_positive() -> Result<()> {
+        let mut world = World::from_input(DATA)?;
+        world.blocked.set(world.blocked.tiles.from_rc(6, 3));
+        verify_that!(
+            world.steps().is_infinite_looping(&mut Vec::new()),
+            is_true()
+        )?;
+        verify_that!(world.steps().is_infinite_looping_brent(), is_true())
+    }
+
+    #[gtest]
+    fn test_obstruction_positions() -> Result<()> {
+        verify_that!(
+            obstruction_positions(DATA)?,
+            eq(&vec![(6, 3), (7, 6), (7, 7), (8, 1), (8, 3), (9, 7)])
+        )
+    }
+
+    #[gtest]
+    fn test_path_crossing_start() -> Result<()> {
+        let data = indoc! {"
+            ##..#.#.
+            .....#.#
+            #.....^#
+            ........
+            ....#...
+            ..#....#
+            .....#..
+            ...##...
+        "};
+        verify_that!(obstruction_positions(data)?, eq(&vec![(5, 6), (7, 6)]))?;
+        verify_that!(part_2(&World::from_input(data)?), eq(2))
+    }
+
+    #[gtest]
+    fn test_explanation() -> Result<()> {
+        verify_that!(
+            explanation(DATA)?,
+            eq(indoc! {"
+                ....#.....
+                ....+XXX+#
+                ....X...X.
+                ..#.X...X.
+                ..+XXX+#X.
+                ..X.X.X.X.
+                .#+O^XXX+.
+                .+XXXXOO#.
+                #OXOXX+X..
+                ......#O..
+            "})
+        )
+    }
+
+    #[gtest]
+    fn test_part2() -> Result<()> {
+        let world = World::from_input(DATA)?;
+        verify_that!(part_2(&world), eq(6))?;
+        let mut brent = looping_obstructions(&world, LoopCheck::Brent);
+        brent.sort();
+        let mut direction_sets = looping_obstructions(&world, LoopCheck::DirectionSets);
+        direction_sets.sort();
+        verify_that!(brent, eq(&direction_sets))
+    }
+}
+
+fn part_1(world: &World) -> usize {
+    let steps = world.steps().map(|player| player.pos);
+    let unique_positions: FxHashSet<_> = steps.collect();
+    unique_positions.len()
+}
+
+fn part_2(world: &World) -> usize {
+    looping_obstructions(world, LoopCheck::DirectionSets).len()
+}
+
+/// The ways of telling whether the guard's walk loops.
+#[derive(Debug, Clone, Copy)]
+enum LoopCheck {
+    DirectionSets,
+    Brent,
+}
+
+/// Where a single new obstruction sends the guard round in a loop.
+fn looping_obstructions(world: &World, check: LoopCheck) -> Vec<usize> {
+    // An obstruction only matters on the guard's path, and only the first
+    // time the guard gets there: any later, it would have blocked them
+    // already.  Each candidate remembers where the guard was just before.
+    // Nothing can go where the guard starts, even if they pass it again.
+    let trace = world.trace();
+    let mut visited = BitGrid::new(world.blocked.tiles.clone());
+    visited.set(world.player.pos);
+    let candidates: Vec<(&Agent, usize)> = trace
+        .iter()
+        .zip(trace.iter().skip(1))
+        .filter(|(_, ahead)| {
+            let first_time = !visited.contains(ahead.pos);
+            visited.set(ahead.pos);
+            first_time
+        })
+        .map(|(player, ahead)| (player, ahead.pos))
+        .collect();
+
+    let obstructions: Vec<usize> = candidates
+        .par_iter()
+        .map_init(
+            || (world.blocked.clone(), Vec::new()),
+            |(blocked, seen), &(player, obstruction)| {
+                blocked.set(obstruction);
+                let speculative_steps = Stepper {
+                    blocked,
+                    player: *player,
+                    exhausted: false,
+                };
+                let loops = match check {
+                    LoopCheck::DirectionSets => speculative_steps.is_infinite_looping(seen),
+                    LoopCheck::Brent => speculative_steps.is_infinite_looping_brent(),
+                };
+                blocked.clear(obstruction);
+                loops.then_some(obstruction)
+            },
+        )
+        .flatten()
+        .collect();
+    log::debug!(
+        "{} of {} obstruction candidates cause a loop",
+        obstructions.len(),
+        candidates.len()
+    );
+    obstructions
+}
+
+/// The row and column of every place a new obstruction would trap the
+/// guard in a loop, in order.
+pub fn obstruction_positions(input: &str) -> Result<Vec<(usize, usize)>, AdventError> {
+    let world = World::from_input(input)?;
+    let mut positions: Vec<(usize, usize)> = looping_obstructions(&world, LoopCheck::DirectionSets)
+        .into_iter()
+        .map(|index| world.blocked.tiles.to_rc(index))
+        .collect();
+    positions.sort();
+    Ok(positions)
+}
+
+/// The map as the puzzle draws it: the guard's path marked `X`, with `+`
+/// where they turn and `O` at each obstruction that would trap them in a
+/// loop.
+pub fn explanation(input: &str) -> Result<String, AdventError> {
+    explanation_with(input, &obstruction_positions(input)?)
+}
+
+/// Like `explanation`, but marking `obstructions`, as found by
+/// `obstruction_positions`, rather than searching for them again.
+pub fn explanation_with(
+    input: &str,
+    obstructions: &[(usize, usize)],
+) -> Result<String, AdventError> {
+    let world = World::from_input(input)?;
+    let trace = world.trace();
+    let tiles = &world.blocked.tiles;
+    let mut map = Grid::from_fn(tiles.width, tiles.height, |index| {
+        if world.blocked.contains(index) {
+            '#'
+        } else {
+            '.'
+        }
+    });
+    for player in &trace {
+        map[player.pos] = 'X';
+    }
+    for (player, next) in trace.iter().zip(trace.iter().skip(1)) {
+        if player.pos == next.pos {
+            map[player.pos] = '+';
+        }
+    }
+    for &(row, col) in obstructions {
+        map[tiles.from_rc(row, col)] = 'O';
+    }
+    map[world.player.pos] = world.player.dir.arrow();
+    Ok(map.render())
+}
+
+pub const PART2_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "direction sets",
+        run: |input| Ok(part_2(&World::from_input(input)?).to_string()),
+    },
+    Variant {
+        name: "brent",
+        run: |input| {
+            let world = World::from_input(input)?;
+            Ok(looping_obstructions(&world, LoopCheck::Brent)
+                .len()
+                .to_string())
+        },
+    },
+];
+
+pub struct Day06;
+
+impl Solver for Day06 {
+    fn title(&self) -> &'static str {
+        "Guard Gallivant"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Simulate the guard; for part 2, try an obstruction at each new step along the path and look for a loop."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        Ok(part_1(&World::from_input(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        Ok(part_2(&World::from_input(input)?).to_string())
+    }
+}
@@ -0,0 +1,397 @@
+use crate::variants::Variant;
+use crate::{AdventError, Solver};
+use rayon::prelude::*;
+
+#[derive(Debug, PartialEq)]
+struct Equation {
+    test_value: u64,
+    args: Box<[u64]>,
+}
+
+impl Equation {
+    fn is_valid(&self) -> bool {
+        is_valid(self.test_value, self.args.as_ref())
+    }
+
+    fn is_valid2(&self) -> bool {
+        is_valid2(self.test_value, self.args.as_ref())
+    }
+}
+
+fn is_valid(test_val: u64, args: &[u64]) -> bool {
+    if args.is_empty() {
+        return false;
+    } else if args.len() == 1 {
+        return test_val == args[0];
+    }
+
+    let last = *args.last().unwrap();
+
+    if test_val >= last && is_valid(test_val - last, &args[0..args.len() - 1]) {
+        return true;
+    }
+
+    // Anything times zero is zero, whatever came before.
+    if last == 0 && test_val == 0 {
+        return true;
+    }
+
+    if test_val.checked_rem(last) == Some(0)
+        && test_val
+            .checked_div(last)
+            .is_some_and(|v| is_valid(v, &args[0..args.len() - 1]))
+    {
+        return true;
+    }
+
+    false
+}
+
+fn is_valid2(test_val: u64, args: &[u64]) -> bool {
+    if args.is_empty() {
+        return false;
+    } else if args.len() == 1 {
+        return test_val == args[0];
+    }
+
+    let last = *args.last().unwrap();
+
+    if test_val >= last && is_valid2(test_val - last, &args[0..args.len() - 1]) {
+        return true;
+    }
+
+    // Anything times zero is zero, whatever came before.
+    if last == 0 && test_val == 0 {
+        return true;
+    }
+
+    if test_val.checked_rem(last) == Some(0)
+        && test_val
+            .checked_div(last)
+            .is_some_and(|v| is_valid2(v, &args[0..args.len() - 1]))
+    {
+        return true;
+    }
+
+    if let Some(v) = try_unconcat(test_val, last) {
+        is_valid2(v, &args[0..args.len() - 1])
+    } else {
+        false
+    }
+}
+
+/// Like `is_valid` and `is_valid2`, but searching forwards from the first
+/// argument, with a stack of partial results rather than recursion.  Every
+/// operator only makes the result bigger, as long as no argument is zero,
+/// so a partial result past the test value is a dead end; concatenation is
+/// skipped outright when it would have more digits than the test value.
+fn is_valid_forward(test_val: u64, args: &[u64], concat: bool) -> bool {
+    let Some((&first, _)) = args.split_first() else {
+        return false;
+    };
+    let prune = !args.contains(&0);
+    let target_digits = count_digits(test_val);
+    // Each entry is a partial result and how many arguments it's used.
+    let mut stack = vec![(first, 1)];
+    while let Some((acc, used)) = stack.pop() {
+        if prune && acc > test_val {
+            continue;
+        }
+        let Some(&next) = args.get(used) else {
+            if acc == test_val {
+                return true;
+            }
+            continue;
+        };
+        if let Some(sum) = acc.checked_add(next) {
+            stack.push((sum, used + 1));
+        }
+        if let Some(product) = acc.checked_mul(next) {
+            stack.push((product, used + 1));
+        }
+        if concat && count_digits(acc) + count_digits(next) <= target_digits {
+            if let Some(joined) = try_concat(acc, next) {
+                stack.push((joined, used + 1));
+            }
+        }
+    }
+    false
+}
+
+impl std::str::FromStr for Equation {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, String> {
+        // A number too big for a u64 is an error rather than skipped, so
+        // it can't quietly drop out of an equation.
+        let mut vals = s.split([' ', ':']).filter(|v| !v.is_empty()).map(|v| {
+            v.parse::<u64>()
+                .map_err(|e| format!("Bad number {:?}: {}", v, e))
+        });
+
+        let test_value: u64 = vals.next().ok_or_else(|| "No test value".to_string())??;
+        let args: Vec<u64> = vals.collect::<Result<_, _>>()?;
+
+        Ok(Equation {
+            test_value,
+            args: args.into(),
+        })
+    }
+}
+
+fn count_digits(n: u64) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
+}
+
+/// `n` with `suffix`'s digits written after it, unless that overflows.
+fn try_concat(n: u64, suffix: u64) -> Option<u64> {
+    n.checked_mul(10u64.checked_pow(count_digits(suffix))?)?
+        .checked_add(suffix)
+}
+
+fn try_unconcat(n: u64, suffix: u64) -> Option<u64> {
+    if n < suffix {
+        None
+    } else {
+        // A suffix too long for its power of ten to fit in a u64 can only
+        // have been written after a zero.
+        let Some(modulo) = 10u64.checked_pow(count_digits(suffix)) else {
+            return (n == suffix).then_some(0);
+        };
+        if n % modulo == suffix {
+            Some(n / modulo)
+        } else {
+            None
+        }
+    }
+}
+
+// The totals are u128s, since enough test values near `u64::MAX` add up
+// to more than a u64 holds.
+fn part_1(problem: &[Equation]) -> u128 {
+    problem
+        .par_iter()
+        .filter(|e| e.is_valid())
+        .map(|e| u128::from(e.test_value))
+        .sum()
+}
+
+fn part_2(problem: &[Equation]) -> u128 {
+    problem
+        .par_iter()
+        .filter(|e| e.is_valid2())
+        .map(|e| u128::from(e.test_value))
+        .sum()
+}
+
+fn parse(input: &str) -> Result<Vec<Equation>, String> {
+    input.lines().map(str::parse::<Equation>).collect()
+}
+
+fn sum_valid_forward(problem: &[Equation], concat: bool) -> u128 {
+    problem
+        .par_iter()
+        .filter(|e| is_valid_forward(e.test_value, &e.args, concat))
+        .map(|e| u128::from(e.test_value))
+        .sum()
+}
+
+pub const PART1_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "reverse recursion",
+        run: |input| Ok(part_1(&parse(input)?).to_string()),
+    },
+    Variant {
+        name: "forward stack",
+        run: |input| Ok(sum_valid_forward(&parse(input)?, false).to_string()),
+    },
+];
+
+pub const PART2_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "reverse recursion",
+        run: |input| Ok(part_2(&parse(input)?).to_string()),
+    },
+    Variant {
+        name: "forward stack",
+        run: |input| Ok(sum_valid_forward(&parse(input)?, true).to_string()),
+    },
+];
+
+pub struct Day07;
+
+impl Solver for Day07 {
+    fn title(&self) -> &'static str {
+        "Bridge Repair"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Work backwards from the test value, undoing multiplication, addition and concatenation."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        Ok(part_1(&parse(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        Ok(part_2(&parse(input)?).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    const DATA: &str = crate::fixtures::DAY07.input;
+
+    #[gtest]
+    fn test_parse() -> Result<()> {
+        let mut parsed = DATA.lines().map(str::parse::<Equation>);
+        verify_that!(
+            parsed.next(),
+            some(ok(eq(&Equation {
+                test_value: 190,
+                args: vec![10, 19].into()
+            })))
+        )?;
+        verify_that!(
+            parsed.next(),
+            some(ok(eq(&Equation {
+                test_value: 3267,
+                args: vec![81, 40, 27].into()
+            })))
+        )?;
+
+        // Check parsing of last line:
+        verify_that!(
+            parsed.next_back(),
+            some(ok(eq(&Equation {
+                test_value: 292,
+                args: vec![11, 6, 16, 20].into()
+            })))
+        )?;
+
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_is_valid() -> Result<()> {
+        verify_that!(
+            "190: 10 19".parse::<Equation>().unwrap().is_valid(),
+            is_true()
+        )?;
+        verify_that!(
+            "3267: 81 40 27".parse::<Equation>().unwrap().is_valid(),
+            is_true()
+        )?;
+
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_part_1() -> Result<()> {
+        let problem = DATA
+            .lines()
+            .map(str::parse::<Equation>)
+            .collect::<std::result::Result<Vec<Equation>, _>>()
+            .unwrap();
+
+        verify_that!(part_1(&problem), eq(3749))
+    }
+
+    #[gtest]
+    fn test_is_valid_forward() -> Result<()> {
+        for equation in parse(DATA).into_test_result()? {
+            let (test_value, args) = (equation.test_value, &equation.args);
+            verify_that!(
+                (test_value, is_valid_forward(test_value, args, false)),
+                eq((test_value, equation.is_valid()))
+            )?;
+            verify_that!(
+                (test_value, is_valid_forward(test_value, args, true)),
+                eq((test_value, equation.is_valid2()))
+            )?;
+        }
+        // A zero can bring an overshooting result back down.
+        verify_that!(is_valid_forward(5, &[9, 9, 0, 5], false), is_true())?;
+        // The searches backwards mustn't divide by a zero argument.
+        for (test_value, args) in [
+            (5, vec![9, 9, 0, 5]),
+            (0, vec![3, 0]),
+            (0, vec![0, 0]),
+            (7, vec![0, 7]),
+            (70, vec![7, 0]),
+            (81, vec![9, 0, 9]),
+            (905, vec![9, 0, 5]),
+            (3, vec![4, 0]),
+        ] {
+            let equation = Equation {
+                test_value,
+                args: args.into(),
+            };
+            let args = &equation.args;
+            verify_that!(
+                (test_value, is_valid_forward(test_value, args, false)),
+                eq((test_value, equation.is_valid()))
+            )?;
+            verify_that!(
+                (test_value, is_valid_forward(test_value, args, true)),
+                eq((test_value, equation.is_valid2()))
+            )?;
+        }
+        verify_that!(is_valid_forward(5, &[], true), is_false())
+    }
+
+    #[gtest]
+    fn test_count_digits() -> Result<()> {
+        verify_that!(count_digits(0), eq(1))?;
+        verify_that!(count_digits(1), eq(1))?;
+        verify_that!(count_digits(5), eq(1))?;
+        verify_that!(count_digits(9), eq(1))?;
+        verify_that!(count_digits(10), eq(2))?;
+        verify_that!(count_digits(99), eq(2))?;
+        verify_that!(count_digits(100), eq(3))?;
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_try_unconcat() -> Result<()> {
+        verify_that!(try_unconcat(156, 6), some(eq(15)))?;
+        verify_that!(try_unconcat(178, 78), some(eq(1)))?;
+        verify_that!(try_concat(15, 6), some(eq(156)))?;
+        verify_that!(try_concat(u64::MAX / 10, 99), none())?;
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_near_u64_max() -> Result<()> {
+        let max = u64::MAX;
+        verify_that!(try_unconcat(max, max), some(eq(0)))?;
+        verify_that!(try_unconcat(max, max - 1), none())?;
+        verify_that!(try_concat(1, max), none())?;
+        verify_that!(is_valid2(max, &[max / 2, 2, 1]), is_true())?;
+        verify_that!(is_valid2(max, &[1844674407370955161, 5]), is_true())?;
+        verify_that!(is_valid_forward(max, &[max, max], true), is_false())?;
+        verify_that!(is_valid_forward(max, &[max / 2, 2, 1], true), is_true())?;
+        let input = format!("{}: {}\n{}: {}\n", max, max, max, max);
+        verify_that!(
+            part_2(&parse(&input).into_test_result()?),
+            eq(2 * max as u128)
+        )?;
+        verify_that!(
+            "18446744073709551616: 1 2".parse::<Equation>(),
+            err(anything())
+        )
+    }
+
+    #[gtest]
+    fn test_part_2() -> Result<()> {
+        let problem = DATA
+            .lines()
+            .map(str::parse::<Equation>)
+            .collect::<std::result::Result<Vec<Equation>, _>>()
+            .unwrap();
+
+        verify_that!(part_2(&problem), eq(11387))
+    }
+}
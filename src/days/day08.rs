@@ -0,0 +1,300 @@
+use crate::{
+    unordered_pairs, AdventError, FromInput, FxHashMap, FxHashSet, Grid, Render, Solver, TileIndex,
+    Vec2,
+};
+
+#[derive(Debug, PartialEq)]
+struct Field {
+    /// Where the antennas are, by frequency.
+    frequencies: FxHashMap<char, Vec<Vec2>>,
+    tiles: TileIndex,
+}
+
+impl FromInput for Field {
+    fn from_input(input: &str) -> Result<Self, AdventError> {
+        let grid = Grid::parse_chars(input, |ch| ch)?;
+        let mut frequencies: FxHashMap<char, Vec<Vec2>> = FxHashMap::default();
+        for index in grid.find_all(char::is_ascii_alphanumeric) {
+            frequencies
+                .entry(grid[index])
+                .or_default()
+                .push(Vec2::from_index(&grid.tiles, index));
+        }
+
+        Ok(Self {
+            frequencies,
+            tiles: grid.tiles,
+        })
+    }
+}
+
+impl Field {
+    /// Every ordered pair of distinct antennas on the same frequency.
+    fn pairs(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        self.frequencies
+            .values()
+            .flat_map(|positions| unordered_pairs(positions).flat_map(|(&a, &b)| [(a, b), (b, a)]))
+    }
+
+    /// The tile index of each antinode of every pair of antennas under
+    /// `model`.  Tiles repeat when more than one pair puts an antinode
+    /// there.
+    pub fn antinodes(&self, model: HarmonicModel) -> impl Iterator<Item = usize> + '_ {
+        let (min, max) = model.multiples();
+        let count = max.checked_sub(min).map_or(0, |n| n.saturating_add(1));
+        // Past this many steps, any line has left the field.
+        let min = min.min(self.tiles.width + self.tiles.height);
+        self.pairs().flat_map(move |(from, to)| {
+            let delta = to - from;
+            std::iter::successors(Some(from + delta * min as i64), move |&pos| {
+                Some(pos + delta)
+            })
+            .take(count)
+            .map_while(|pos| pos.to_index(&self.tiles))
+        })
+    }
+}
+
+/// Which points in line with two antennas of a frequency are antinodes,
+/// as multiples of the distance between them, measured from the first:
+/// the second antenna is one distance away.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HarmonicModel {
+    /// Only the point twice as far from one antenna as the other, as in
+    /// part 1.
+    Double,
+    /// Every point at a whole multiple of the distance, the antennas
+    /// included, as in part 2.
+    AllMultiples,
+    /// Every multiple from `min` to `max`, inclusive.
+    Multiples { min: usize, max: usize },
+}
+
+impl HarmonicModel {
+    fn multiples(self) -> (usize, usize) {
+        match self {
+            HarmonicModel::Double => (2, 2),
+            HarmonicModel::AllMultiples => (1, usize::MAX),
+            HarmonicModel::Multiples { min, max } => (min, max),
+        }
+    }
+}
+
+/// Reads `MIN..MAX`, or `MIN..` for no upper limit.
+impl std::str::FromStr for HarmonicModel {
+    type Err = AdventError;
+    fn from_str(s: &str) -> Result<Self, AdventError> {
+        let (min, max) = s
+            .split_once("..")
+            .ok_or_else(|| AdventError::parse(format!("Expected MIN..MAX, got {:?}", s)))?;
+        let min = min.parse()?;
+        let max = if max.is_empty() {
+            usize::MAX
+        } else {
+            max.parse()?
+        };
+        Ok(HarmonicModel::Multiples { min, max })
+    }
+}
+
+impl Field {
+    /// The field drawn with `#` at each of `antinodes`, except where an
+    /// antenna is in the way.
+    fn render_antinodes(&self, antinodes: &FxHashSet<usize>) -> String {
+        let mut grid = Grid::from_fn(self.tiles.width, self.tiles.height, |_| '.');
+        for &index in antinodes {
+            grid[index] = '#';
+        }
+        for (&label, positions) in &self.frequencies {
+            for index in positions.iter().filter_map(|pos| pos.to_index(&self.tiles)) {
+                grid[index] = label;
+            }
+        }
+        grid.render()
+    }
+}
+
+fn count_antinodes(field: &Field, model: HarmonicModel) -> usize {
+    let unique_locations: FxHashSet<_> = field.antinodes(model).collect();
+    unique_locations.len()
+}
+
+fn part_1(field: &Field) -> usize {
+    count_antinodes(field, HarmonicModel::Double)
+}
+
+fn part_2(field: &Field) -> usize {
+    count_antinodes(field, HarmonicModel::AllMultiples)
+}
+
+/// How many distinct places are antinodes under `model`.
+pub fn count_antinodes_with(input: &str, model: HarmonicModel) -> Result<usize, AdventError> {
+    Ok(count_antinodes(&Field::from_input(input)?, model))
+}
+
+/// The row and column of each antinode under `model`, in order.
+pub fn antinode_positions(
+    input: &str,
+    model: HarmonicModel,
+) -> Result<Vec<(usize, usize)>, AdventError> {
+    let field = Field::from_input(input)?;
+    let antinodes: FxHashSet<usize> = field.antinodes(model).collect();
+    let mut positions: Vec<(usize, usize)> = antinodes
+        .iter()
+        .map(|&index| field.tiles.to_rc(index))
+        .collect();
+    positions.sort();
+    Ok(positions)
+}
+
+/// The field with each part's antinodes drawn in, as in the puzzle's
+/// examples.
+pub fn explanation(input: &str) -> Result<String, AdventError> {
+    let field = Field::from_input(input)?;
+    let part1 = field.render_antinodes(&field.antinodes(HarmonicModel::Double).collect());
+    let part2 = field.render_antinodes(&field.antinodes(HarmonicModel::AllMultiples).collect());
+    Ok(format!("Antinodes:\n{}\nWith harmonics:\n{}", part1, part2))
+}
+
+pub struct Day08;
+
+impl Solver for Day08 {
+    fn title(&self) -> &'static str {
+        "Resonant Collinearity"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Pair up same-frequency antennas and step along the line between them."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        Ok(part_1(&Field::from_input(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        Ok(part_2(&Field::from_input(input)?).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use indoc::indoc;
+
+    const DATA: &str = crate::fixtures::DAY08.input;
+
+    #[gtest]
+    fn test_parse() -> Result<()> {
+        let field = Field::from_input(DATA)?;
+        verify_that!(field.frequencies.len(), eq(2))?;
+        verify_that!(
+            field.frequencies[&'0'],
+            eq(&vec![Vec2(8, 1), Vec2(5, 2), Vec2(7, 3), Vec2(4, 4)])
+        )?;
+        verify_that!(
+            field.frequencies[&'A'],
+            eq(&vec![Vec2(6, 5), Vec2(8, 8), Vec2(9, 9)])
+        )
+    }
+
+    #[gtest]
+    fn test_antinode_positions() -> Result<()> {
+        let positions = antinode_positions(DATA, HarmonicModel::Double)?;
+        verify_that!(positions.len(), eq(14))?;
+        verify_that!(positions[..3], eq(&[(0, 6), (0, 11), (1, 3)]))?;
+        verify_that!(
+            antinode_positions(DATA, HarmonicModel::AllMultiples)?.len(),
+            eq(34)
+        )
+    }
+
+    #[gtest]
+    fn test_antinodes_in_line() -> Result<()> {
+        let mut input = "T.........\n...T......\n.T........\n".to_string();
+        input += &"..........\n".repeat(7);
+        let field = Field::from_input(&input)?;
+        let antinodes: FxHashSet<usize> = field.antinodes(HarmonicModel::AllMultiples).collect();
+        verify_that!(antinodes.len(), eq(9))?;
+        // Each one is in line with some pair of antennas.
+        for index in antinodes {
+            let pos = Vec2::from_index(&field.tiles, index);
+            let in_line = field.pairs().any(|(from, to)| {
+                let (a, b) = (to - from, pos - from);
+                a.0 * b.1 == a.1 * b.0
+            });
+            verify_that!((pos, in_line), eq((pos, true)))?;
+        }
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_harmonic_models() -> Result<()> {
+        let field = Field::from_input("T.........\n...T......\n.T........\n..........")?;
+        let multiples = |min, max| HarmonicModel::Multiples { min, max };
+        verify_that!(count_antinodes(&field, HarmonicModel::Double), eq(2))?;
+        verify_that!(
+            count_antinodes(&field, multiples(2, 2)),
+            eq(count_antinodes(&field, HarmonicModel::Double))
+        )?;
+        // Just the antennas themselves.
+        verify_that!(count_antinodes(&field, multiples(1, 1)), eq(3))?;
+        verify_that!(count_antinodes(&field, multiples(3, 1)), eq(0))?;
+        verify_that!(count_antinodes(&field, multiples(1000, usize::MAX)), eq(0))?;
+        let field = Field::from_input(DATA)?;
+        verify_that!(
+            count_antinodes(&field, "1..".parse()?),
+            eq(count_antinodes(&field, HarmonicModel::AllMultiples))
+        )?;
+        verify_that!("2..5".parse::<HarmonicModel>()?, eq(multiples(2, 5)))?;
+        verify_that!("2".parse::<HarmonicModel>(), err(anything()))
+    }
+
+    #[gtest]
+    fn test_explanation() -> Result<()> {
+        verify_that!(
+            explanation(DATA)?,
+            eq(indoc! {"
+                Antinodes:
+                ......#....#
+                ...#....0...
+                ....#0....#.
+                ..#....0....
+                ....0....#..
+                .#....A.....
+                ...#........
+                #......#....
+                ........A...
+                .........A..
+                ..........#.
+                ..........#.
+
+                With harmonics:
+                ##....#....#
+                .#.#....0...
+                ..#.#0....#.
+                ..##...0....
+                ....0....#..
+                .#...#A....#
+                ...#..#.....
+                #....#.#....
+                ..#.....A...
+                ....#....A..
+                .#........#.
+                ...#......##
+            "})
+        )
+    }
+
+    #[gtest]
+    fn test_part1() -> Result<()> {
+        let field = Field::from_input(DATA)?;
+        verify_that!(part_1(&field), eq(14))
+    }
+
+    #[gtest]
+    fn test_part2() -> Result<()> {
+        let field = Field::from_input(DATA)?;
+        verify_that!(part_2(&field), eq(34))
+    }
+}
@@ -0,0 +1,623 @@
+use crate::interval::{Interval, IntervalSet};
+use crate::variants::Variant;
+use crate::{AdventError, Solver};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, VecDeque};
+
+#[derive(Debug, PartialEq)]
+enum DiskEntry {
+    File { id: usize, len: usize },
+    Free(usize),
+}
+impl DiskEntry {
+    fn parse(s: &str) -> Result<Vec<DiskEntry>, AdventError> {
+        let digits = s
+            .trim()
+            .chars()
+            .enumerate()
+            .map(|(col, ch)| {
+                ch.to_digit(10).map(|digit| digit as usize).ok_or_else(|| {
+                    AdventError::parse(format!(
+                        "Expected a digit at row 1, column {}, got {:?}",
+                        col + 1,
+                        ch
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut digits = digits.into_iter();
+
+        let mut results = Vec::new();
+        let mut id = 0;
+        while let Some(file) = digits.next() {
+            results.push(DiskEntry::File { id, len: file });
+            id += 1;
+
+            let Some(free) = digits.next() else {
+                break;
+            };
+            results.push(DiskEntry::Free(free));
+        }
+
+        Ok(results)
+    }
+}
+
+trait DefragByEntry {
+    fn defrag_by_entry(&mut self);
+
+    /// The same whole-file defrag, but keeping the free space as a
+    /// min-heap of offsets per gap size, so the leftmost gap that fits is
+    /// the best of a handful of heap tops rather than a scan of the disk.
+    fn defrag_by_size_heaps(&mut self);
+}
+
+impl DefragByEntry for Vec<DiskEntry> {
+    fn defrag_by_entry(&mut self) {
+        // Each file's extent on disk, and the disk's free space.
+        let mut files: Vec<(usize, Interval)> = Vec::new();
+        let mut free = IntervalSet::new();
+
+        let mut offset = 0;
+        for entry in self.iter() {
+            match *entry {
+                DiskEntry::File { id, len } => {
+                    files.push((id, Interval::new(offset, offset + len as i64)));
+                    offset += len as i64;
+                }
+                DiskEntry::Free(len) => {
+                    free.insert(Interval::new(offset, offset + len as i64));
+                    offset += len as i64;
+                }
+            }
+        }
+
+        for (_, extent) in files.iter_mut().rev() {
+            // Space the file leaves behind is never to the left of a file
+            // still to move, so it's safe to free straight away.
+            let slot = free
+                .iter()
+                .take_while(|slot| slot.start < extent.start)
+                .find(|slot| slot.len() >= extent.len());
+            if let Some(slot) = slot {
+                let moved = Interval::new(slot.start, slot.start + extent.len());
+                free.remove(moved);
+                free.insert(*extent);
+                *extent = moved;
+            }
+        }
+
+        // Finally, restructure in terms of the relocations.
+        let mut items: Vec<(i64, DiskEntry)> = files
+            .into_iter()
+            .map(|(id, extent)| {
+                let len = extent.len() as usize;
+                (extent.start, DiskEntry::File { id, len })
+            })
+            .chain(
+                free.iter()
+                    .map(|extent| (extent.start, DiskEntry::Free(extent.len() as usize))),
+            )
+            .collect();
+        items.sort_by_key(|t| t.0);
+        *self = items.into_iter().map(|t| t.1).collect();
+    }
+
+    fn defrag_by_size_heaps(&mut self) {
+        // Each file as (offset, id, len), and the gaps' offsets by size.
+        let mut files: Vec<(usize, usize, usize)> = Vec::new();
+        let mut gaps: Vec<BinaryHeap<Reverse<usize>>> = Vec::new();
+        let mut offset = 0;
+        for entry in self.iter() {
+            match *entry {
+                DiskEntry::File { id, len } => {
+                    files.push((offset, id, len));
+                    offset += len;
+                }
+                DiskEntry::Free(len) => {
+                    if gaps.len() <= len {
+                        gaps.resize(len + 1, BinaryHeap::new());
+                    }
+                    gaps[len].push(Reverse(offset));
+                    offset += len;
+                }
+            }
+        }
+        let disk_len = offset;
+
+        // As before, space a file leaves behind never matters to the files
+        // still to move, so it needn't go back on the heaps.
+        for (offset, _, len) in files.iter_mut().rev() {
+            let best = gaps
+                .iter()
+                .enumerate()
+                .skip(*len)
+                .filter_map(|(size, heap)| Some((heap.peek()?.0, size)))
+                .filter(|&(gap, _)| gap < *offset)
+                .min();
+            if let Some((gap, size)) = best {
+                gaps[size].pop();
+                gaps[size - *len].push(Reverse(gap + *len));
+                *offset = gap;
+            }
+        }
+
+        // The free space is whatever's between the files.
+        files.sort();
+        let mut layout = Vec::new();
+        let mut end = 0;
+        for (offset, id, len) in files {
+            if offset > end {
+                layout.push(DiskEntry::Free(offset - end));
+            }
+            layout.push(DiskEntry::File { id, len });
+            end = end.max(offset + len);
+        }
+        if disk_len > end {
+            layout.push(DiskEntry::Free(disk_len - end));
+        }
+        *self = layout;
+    }
+}
+
+/// Part 1's block-by-block compaction, done a run of blocks at a time:
+/// each gap, front to back, takes blocks from the files at the back.
+/// Never lays out the disk block by block, so the disk's size doesn't
+/// matter, only how many entries describe it.
+fn compact_blocks(entries: &[DiskEntry]) -> Vec<DiskEntry> {
+    let mut files: VecDeque<(usize, usize)> = entries
+        .iter()
+        .filter_map(|entry| match *entry {
+            DiskEntry::File { id, len } => Some((id, len)),
+            DiskEntry::Free(_) => None,
+        })
+        .collect();
+    let mut compacted = Vec::new();
+    for entry in entries {
+        match *entry {
+            DiskEntry::File { .. } => {
+                // Whatever's left of this file stays put.
+                let Some((id, len)) = files.pop_front() else {
+                    break;
+                };
+                compacted.push(DiskEntry::File { id, len });
+            }
+            DiskEntry::Free(mut gap) => {
+                while gap > 0 {
+                    let Some((id, len)) = files.back_mut() else {
+                        break;
+                    };
+                    let moved = gap.min(*len);
+                    compacted.push(DiskEntry::File {
+                        id: *id,
+                        len: moved,
+                    });
+                    gap -= moved;
+                    *len -= moved;
+                    if *len == 0 {
+                        files.pop_back();
+                    }
+                }
+            }
+        }
+    }
+    let free = disk_len(entries) - disk_len(&compacted);
+    if free > 0 {
+        compacted.push(DiskEntry::Free(free));
+    }
+    compacted
+}
+
+fn disk_len(entries: &[DiskEntry]) -> usize {
+    entries
+        .iter()
+        .map(|entry| match *entry {
+            DiskEntry::File { len, .. } | DiskEntry::Free(len) => len,
+        })
+        .sum()
+}
+
+/// The checksum straight from the entries: a file of `len` blocks from
+/// `offset` adds `id` times the sum of `offset..offset + len`.
+fn checksum(entries: &[DiskEntry]) -> usize {
+    let mut offset = 0;
+    let mut sum = 0;
+    for entry in entries {
+        match *entry {
+            DiskEntry::File { id, len } => {
+                sum += id * (len * offset + len * len.saturating_sub(1) / 2);
+                offset += len;
+            }
+            DiskEntry::Free(len) => offset += len,
+        }
+    }
+    sum
+}
+
+/// Whether `after` is a rearrangement of `before`: a disk of the same
+/// size, with each file still on it at its full length, even if split up.
+fn check_layout(before: &[DiskEntry], after: &[DiskEntry]) -> Result<(), String> {
+    if disk_len(after) != disk_len(before) {
+        return Err(format!(
+            "Disk is {} blocks, but was {}",
+            disk_len(after),
+            disk_len(before)
+        ));
+    }
+    let file_lens = |entries: &[DiskEntry]| {
+        let mut lens: BTreeMap<usize, usize> = BTreeMap::new();
+        for entry in entries {
+            if let DiskEntry::File { id, len } = *entry {
+                *lens.entry(id).or_default() += len;
+            }
+        }
+        lens
+    };
+    let (was, now) = (file_lens(before), file_lens(after));
+    for id in was.keys().chain(now.keys()) {
+        let (was, now) = (was.get(id).unwrap_or(&0), now.get(id).unwrap_or(&0));
+        if was != now {
+            return Err(format!("File {} is {} blocks, but was {}", id, now, was));
+        }
+    }
+    Ok(())
+}
+
+/// Runs every defrag on the disk and checks each leaves it consistent,
+/// logging each layout.
+pub fn check_defrags(input: &str) -> Result<(), String> {
+    let parse = || DiskEntry::parse(input).map_err(|e| e.to_string());
+    let entries = parse()?;
+    let mut by_entry = parse()?;
+    by_entry.defrag_by_entry();
+    let mut by_heaps = parse()?;
+    by_heaps.defrag_by_size_heaps();
+    for (name, layout) in [
+        ("blocks", compact_blocks(&entries)),
+        ("interval set", by_entry),
+        ("size heaps", by_heaps),
+    ] {
+        log::debug!("{}: {}", name, Layout(&layout));
+        check_layout(&entries, &layout).map_err(|e| format!("{}: {}", name, e))?;
+    }
+    Ok(())
+}
+
+/// Draws a layout as the puzzle does, a digit per block and `.` for free
+/// space, without building the block map.
+struct Layout<'a>(&'a [DiskEntry]);
+
+impl std::fmt::Display for Layout<'_> {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        for entry in self.0 {
+            match *entry {
+                DiskEntry::File { id, len } => {
+                    for _ in 0..len {
+                        write!(formatter, "{}", id)?;
+                    }
+                }
+                DiskEntry::Free(len) => write!(formatter, "{}", ".".repeat(len))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct DiskMap(Vec<Option<usize>>);
+impl DiskMap {
+    fn defrag_by_block(&mut self) {
+        let n = self.0.len();
+        let mut i: usize = 0;
+        let mut j: isize = (n - 1) as isize;
+        loop {
+            // Scan i forward for next vacant spot
+            while i < n {
+                if self.0[i].is_none() {
+                    break;
+                }
+                i += 1;
+            }
+            if i == n {
+                break;
+            }
+
+            // Scan j backward for next occupied spot
+            while j >= 0 {
+                if self.0[j as usize].is_some() {
+                    break;
+                }
+                j -= 1;
+            }
+            if j < 0 {
+                break;
+            }
+
+            // If the pointers crossed, we're done.
+            if i >= j as usize {
+                break;
+            }
+            // Swap, and try again.
+            self.0.swap(i, j as usize);
+        }
+    }
+
+    fn checksum(&self) -> usize {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(position, entry)| match entry {
+                Some(id) => position * id,
+                None => 0,
+            })
+            .sum()
+    }
+}
+
+impl<'a> FromIterator<&'a DiskEntry> for DiskMap {
+    fn from_iter<T>(entries: T) -> Self
+    where
+        T: IntoIterator<Item = &'a DiskEntry>,
+    {
+        let entries: Vec<_> = entries.into_iter().collect();
+        let capacity: usize = entries
+            .iter()
+            .map(|entry| match entry {
+                DiskEntry::File { len, .. } => len,
+                DiskEntry::Free(len) => len,
+            })
+            .sum();
+        let mut buffer: Vec<Option<usize>> = Vec::with_capacity(capacity);
+        for entry in entries {
+            match entry {
+                DiskEntry::File { len, id } => {
+                    for _ in 0..*len {
+                        buffer.push(Some(*id));
+                    }
+                }
+                DiskEntry::Free(len) => {
+                    for _ in 0..*len {
+                        buffer.push(None);
+                    }
+                }
+            }
+        }
+        Self(buffer)
+    }
+}
+
+impl std::fmt::Display for DiskMap {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        for entry in &self.0 {
+            match entry {
+                None => {
+                    write!(formatter, ".")?;
+                }
+                Some(v) => {
+                    write!(formatter, "{}", v)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Day09;
+
+impl Solver for Day09 {
+    fn title(&self) -> &'static str {
+        "Disk Fragmenter"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Compact block by block for part 1 and whole file by whole file for part 2."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        Ok(checksum(&compact_blocks(&DiskEntry::parse(input)?)).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        let mut entries = DiskEntry::parse(input)?;
+        entries.defrag_by_size_heaps();
+        Ok(checksum(&entries).to_string())
+    }
+}
+
+pub const PART1_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "extents",
+        run: |input| Day09.part1(input),
+    },
+    Variant {
+        name: "block map",
+        run: |input| {
+            let entries = DiskEntry::parse(input)?;
+            let mut diskmap: DiskMap = entries.iter().collect();
+            diskmap.defrag_by_block();
+            Ok(diskmap.checksum().to_string())
+        },
+    },
+];
+
+pub const PART2_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "size heaps",
+        run: |input| Day09.part2(input),
+    },
+    Variant {
+        name: "interval set",
+        run: |input| {
+            let mut entries = DiskEntry::parse(input)?;
+            entries.defrag_by_entry();
+            let diskmap: DiskMap = entries.iter().collect();
+            Ok(diskmap.checksum().to_string())
+        },
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    const DATA: &str = crate::fixtures::DAY09.input;
+
+    #[gtest]
+    fn test_parse_entries() -> Result<()> {
+        verify_that!(
+            DiskEntry::parse("1234")?,
+            elements_are![
+                eq(&DiskEntry::File { id: 0, len: 1 }),
+                eq(&DiskEntry::Free(2)),
+                eq(&DiskEntry::File { id: 1, len: 3 }),
+                eq(&DiskEntry::Free(4))
+            ]
+        )
+    }
+
+    #[gtest]
+    fn test_unexpected_character() -> Result<()> {
+        verify_that!(DiskEntry::parse("12-45"), err(anything()))?;
+        verify_that!(Day09.part1("12-45"), err(anything()))?;
+        verify_that!(Day09.part2("12-45"), err(anything()))
+    }
+
+    #[gtest]
+    fn test_parse_diskmap() -> Result<()> {
+        verify_that!(
+            DiskEntry::parse("1234")?.iter().collect::<DiskMap>(),
+            eq(&DiskMap(vec![
+                Some(0),
+                None,
+                None,
+                Some(1),
+                Some(1),
+                Some(1),
+                None,
+                None,
+                None,
+                None
+            ]))
+        )
+    }
+
+    #[gtest]
+    fn test_defrag() -> Result<()> {
+        let mut diskmap: DiskMap = DiskEntry::parse(DATA)?.iter().collect();
+        println!("{}", diskmap);
+        diskmap.defrag_by_block();
+        verify_that!(
+            diskmap.to_string(),
+            eq("0099811188827773336446555566..............")
+        )
+    }
+
+    #[gtest]
+    fn test_checksum() -> Result<()> {
+        let mut diskmap: DiskMap = DiskEntry::parse(DATA)?.iter().collect();
+        diskmap.defrag_by_block();
+        verify_that!(diskmap.checksum(), eq(1928))
+    }
+
+    #[gtest]
+    fn test_defrag_by_entry() -> Result<()> {
+        let mut entries = DiskEntry::parse(DATA)?;
+        entries.defrag_by_entry();
+        verify_that!(
+            entries.iter().collect::<DiskMap>().to_string(),
+            eq("00992111777.44.333....5555.6666.....8888..")
+        )
+    }
+
+    #[gtest]
+    fn test_compact_blocks() -> Result<()> {
+        let compacted = compact_blocks(&DiskEntry::parse(DATA)?);
+        verify_that!(
+            Layout(&compacted).to_string(),
+            eq("0099811188827773336446555566..............")
+        )?;
+        verify_that!(checksum(&compacted), eq(1928))?;
+        for input in ["12345", "1010101010", "9953877292941", "1", "90909"] {
+            let entries = DiskEntry::parse(input)?;
+            let mut diskmap: DiskMap = entries.iter().collect();
+            diskmap.defrag_by_block();
+            let compacted = compact_blocks(&entries);
+            verify_that!(
+                (input, compacted.iter().collect::<DiskMap>()),
+                eq(&(input, diskmap))
+            )?;
+        }
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_layout() -> Result<()> {
+        let entries = DiskEntry::parse(DATA)?;
+        verify_that!(
+            Layout(&entries).to_string(),
+            eq(&entries.iter().collect::<DiskMap>().to_string())
+        )?;
+        verify_that!(
+            Layout(&DiskEntry::parse("12345")?).to_string(),
+            eq("0..111....22222")
+        )
+    }
+
+    #[gtest]
+    fn test_check_layout() -> Result<()> {
+        for input in [DATA, "12345", "1010101010", "9953877292941", "1", "90909"] {
+            verify_that!((input, check_defrags(input)), eq(&(input, Ok(()))))?;
+        }
+        let entries = DiskEntry::parse("12345")?;
+        let file = |id, len| DiskEntry::File { id, len };
+        // A file cut short, one lost, and the disk shrunk.
+        verify_that!(
+            check_layout(
+                &entries,
+                &[file(0, 1), file(2, 5), file(1, 2), DiskEntry::Free(7)]
+            ),
+            err(eq("File 1 is 2 blocks, but was 3"))
+        )?;
+        verify_that!(
+            check_layout(&entries, &[file(0, 1), file(1, 3), DiskEntry::Free(11)]),
+            err(eq("File 2 is 0 blocks, but was 5"))
+        )?;
+        verify_that!(
+            check_layout(&entries, &[file(0, 1), file(2, 5), file(1, 3)]),
+            err(eq("Disk is 9 blocks, but was 15"))
+        )
+    }
+
+    #[gtest]
+    fn test_extent_checksum() -> Result<()> {
+        let mut entries = DiskEntry::parse(DATA)?;
+        entries.defrag_by_entry();
+        let diskmap: DiskMap = entries.iter().collect();
+        verify_that!(checksum(&entries), eq(diskmap.checksum()))
+    }
+
+    #[gtest]
+    fn test_part2() -> Result<()> {
+        let mut entries = DiskEntry::parse(DATA)?;
+        entries.defrag_by_entry();
+        let diskmap: DiskMap = entries.iter().collect();
+        verify_that!(diskmap.checksum(), eq(2858))
+    }
+
+    #[gtest]
+    fn test_defrag_by_size_heaps() -> Result<()> {
+        for input in [DATA, "12345", "1010101010", "9953877292941", "1", "0"] {
+            let mut by_entry = DiskEntry::parse(input)?;
+            by_entry.defrag_by_entry();
+            let mut by_heaps = DiskEntry::parse(input)?;
+            by_heaps.defrag_by_size_heaps();
+            verify_that!(
+                (input, by_heaps.iter().collect::<DiskMap>().to_string()),
+                eq(&(input, by_entry.iter().collect::<DiskMap>().to_string()))
+            )?;
+        }
+        Ok(())
+    }
+}
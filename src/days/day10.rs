@@ -0,0 +1,332 @@
+use crate::search::bfs;
+use crate::variants::Variant;
+use crate::{AdventError, Grid, Memo, Solver};
+
+#[derive(Debug, PartialEq)]
+struct FieldMap {
+    /// Each tile's height, or None for a `.` that can't be walked on.
+    heights: Grid<Option<u8>>,
+}
+
+impl FieldMap {
+    fn new(s: &str) -> Result<Self, AdventError> {
+        let chars = Grid::parse_chars(s, |ch| ch)?;
+        let heights = chars
+            .iter_with_index()
+            .map(|(index, &ch)| match ch {
+                '.' => Ok(None),
+                _ => ch
+                    .to_digit(10)
+                    .map(|digit| Some(digit as u8))
+                    .ok_or_else(|| {
+                        let (row, col) = chars.tiles.to_rc(index);
+                        AdventError::parse(format!(
+                            "Expected a height at row {}, column {}, got {:?}",
+                            row + 1,
+                            col + 1,
+                            ch
+                        ))
+                    }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            heights: Grid::new(heights, chars.tiles.clone()),
+        })
+    }
+
+    fn trailheads(&self) -> impl Iterator<Item = usize> + '_ {
+        self.heights.find_all(|&height| height == Some(0))
+    }
+
+    fn directional_neighbors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.heights.neighbors(index)
+    }
+
+    fn neighbors(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        self.directional_neighbors(i)
+            .filter(move |j| self.heights[i].map(|height| height + 1) == self.heights[*j])
+    }
+
+    /// Every tile reachable from `start` going steadily uphill.
+    fn reachable(&self, start: usize) -> Vec<usize> {
+        bfs(start, |&index| self.neighbors(index))
+            .iter()
+            .map(|(&index, _)| index)
+            .collect()
+    }
+
+    fn trailhead_score(&self, trailhead: usize) -> usize {
+        self.reachable(trailhead)
+            .into_iter()
+            .filter(|index| self.heights[*index] == Some(9))
+            .count()
+    }
+
+    /// The number of uphill paths from `trailhead` to a 9.  Paths only go
+    /// up, so they can't loop back on themselves.
+    fn rating(&self, trailhead: usize) -> usize {
+        let mut paths = Memo::new(|paths: &mut dyn FnMut(usize) -> usize, index: usize| {
+            if self.heights[index] == Some(9) {
+                1
+            } else {
+                self.neighbors(index).map(paths).sum()
+            }
+        });
+        paths.get(trailhead)
+    }
+
+    /// Every tile's rating at once: the number of uphill paths from it to
+    /// a 9.  Going from the 9s down, each tile's neighbors one higher are
+    /// already counted, so it's one pass over the map.
+    fn ratings(&self) -> Vec<usize> {
+        let mut paths = vec![0; self.heights.len()];
+        for height in (0..=9).rev() {
+            for index in self.heights.find_all(|&h| h == Some(height)) {
+                paths[index] = if height == 9 {
+                    1
+                } else {
+                    self.neighbors(index).map(|next| paths[next]).sum()
+                };
+            }
+        }
+        paths
+    }
+}
+
+fn part_1(field_map: &FieldMap) -> usize {
+    field_map
+        .trailheads()
+        .map(|trailhead| field_map.trailhead_score(trailhead))
+        .sum()
+}
+
+fn part_2(field_map: &FieldMap) -> usize {
+    let ratings = field_map.ratings();
+    field_map
+        .trailheads()
+        .map(|trailhead| ratings[trailhead])
+        .sum()
+}
+
+pub const PART2_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "height order",
+        run: |input| Ok(part_2(&FieldMap::new(input)?).to_string()),
+    },
+    Variant {
+        name: "memo per trailhead",
+        run: |input| {
+            let field_map = FieldMap::new(input)?;
+            Ok(field_map
+                .trailheads()
+                .map(|trailhead| field_map.rating(trailhead))
+                .sum::<usize>()
+                .to_string())
+        },
+    },
+];
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    fn title(&self) -> &'static str {
+        "Hoof It"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Search uphill from each trailhead for reachable peaks; for part 2, count paths from the 9s down."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        Ok(part_1(&FieldMap::new(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        Ok(part_2(&FieldMap::new(input)?).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    const DATA: &str = crate::fixtures::DAY10.input;
+
+    #[gtest]
+    fn test_impassable_tiles() -> Result<()> {
+        let field = FieldMap::new(
+            "\
+...0...
+...1...
+...2...
+6543456
+7.....7
+8.....8
+9.....9",
+        )?;
+        verify_that!(part_1(&field), eq(2))?;
+        let field = FieldMap::new(
+            "\
+.....0.
+..4321.
+..5..2.
+..6543.
+..7..4.
+..8765.
+..9....",
+        )?;
+        verify_that!(part_2(&field), eq(3))
+    }
+
+    #[gtest]
+    fn test_unexpected_map_character() -> Result<()> {
+        verify_that!(FieldMap::new("0123\n45x7"), err(anything()))?;
+        verify_that!(Day10.part1("0123\n45x7"), err(anything()))?;
+        verify_that!(Day10.part2("0123\n45x7"), err(anything()))
+    }
+
+    #[gtest]
+    fn test_trailheads() -> Result<()> {
+        let field = FieldMap::new(
+            "\
+0123
+1234
+8765
+9876
+	    ",
+        )
+        .into_test_result()?;
+        verify_that!(field.trailheads().collect::<Vec<_>>(), [eq(&0)])
+    }
+
+    #[gtest]
+    fn test_directional_neighbors() -> Result<()> {
+        let field = FieldMap::new(
+            "\
+0123
+1234
+8765
+	    ",
+        )
+        .into_test_result()?;
+        verify_that!(
+            field.directional_neighbors(0).collect::<Vec<_>>(),
+            unordered_elements_are![eq(&1), eq(&4)]
+        )?;
+        verify_that!(
+            field.directional_neighbors(5).collect::<Vec<_>>(),
+            unordered_elements_are![eq(&6), eq(&4), eq(&1), eq(&9)]
+        )?;
+        verify_that!(
+            field.directional_neighbors(11).collect::<Vec<_>>(),
+            unordered_elements_are![eq(&10), eq(&7)]
+        )?;
+        verify_that!(
+            field.directional_neighbors(10).collect::<Vec<_>>(),
+            unordered_elements_are![eq(&9), eq(&11), eq(&6)]
+        )?;
+
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_neighbors() -> Result<()> {
+        let field = FieldMap::new(
+            "\
+0123
+1234
+8765
+	    ",
+        )
+        .into_test_result()?;
+        verify_that!(
+            field.neighbors(1).collect::<Vec<_>>(),
+            unordered_elements_are![eq(&2), eq(&5)]
+        )?;
+
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_reachable() -> Result<()> {
+        let field = FieldMap::new(
+            "\
+0023
+1234
+8765
+	    ",
+        )
+        .into_test_result()?;
+        verify_that!(
+            field.reachable(0),
+            unordered_elements_are![
+                eq(&0),
+                eq(&4),
+                eq(&5),
+                eq(&6),
+                eq(&7),
+                eq(&8),
+                eq(&9),
+                eq(&10),
+                eq(&11)
+            ]
+        )?;
+
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_trailhead_score() -> Result<()> {
+        let field = FieldMap::new(
+            "\
+0123
+1234
+8765
+9876
+	    ",
+        )
+        .into_test_result()?;
+        verify_that!(field.trailhead_score(0), eq(1))?;
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_part_1() -> Result<()> {
+        let field = FieldMap::new(DATA).into_test_result()?;
+        verify_that!(part_1(&field), eq(36))
+    }
+
+    #[gtest]
+    fn test_rating() -> Result<()> {
+        let data = "\
+0123456789";
+        let field = FieldMap::new(data).into_test_result()?;
+        verify_that!(field.rating(0), eq(1))?;
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_rating_larger() -> Result<()> {
+        let field = FieldMap::new(DATA).into_test_result()?;
+        verify_that!(field.rating(2), eq(20))?;
+        verify_that!(field.rating(4), eq(24))?;
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_ratings() -> Result<()> {
+        let field = FieldMap::new(DATA).into_test_result()?;
+        let ratings = field.ratings();
+        for (index, &rating) in ratings.iter().enumerate() {
+            verify_that!((index, rating), eq((index, field.rating(index))))?;
+        }
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_rating_part_2() -> Result<()> {
+        let field = FieldMap::new(DATA).into_test_result()?;
+        verify_that!(part_2(&field), eq(81))
+    }
+}
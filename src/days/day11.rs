@@ -0,0 +1,226 @@
+use crate::variants::Variant;
+use crate::{AdventError, FxHashMap, Solver};
+
+fn parse(s: &str) -> impl Iterator<Item = Result<u64, std::num::ParseIntError>> + '_ {
+    s.split_whitespace().map(|s| s.parse::<u64>())
+}
+
+fn blink_all(numbers: impl IntoIterator<Item = u64>) -> Result<Vec<u64>, Overflow> {
+    let mut result = Vec::new();
+    for number in numbers {
+        result.extend(blink(number)?);
+    }
+    Ok(result)
+}
+
+#[test]
+fn test_blink_all() {
+    assert_eq!(blink_all([125, 17]), Ok(vec![253000, 1, 7]));
+    assert_eq!(blink_all([253000, 1, 7]), Ok(vec![253, 0, 2024, 14168]));
+}
+
+#[test]
+fn test_count_after() {
+    let counts: Vec<u128> = (0..=6)
+        .map(|blinks| count_after(&[125, 17], blinks).unwrap())
+        .collect();
+    assert_eq!(counts, vec![2, 3, 4, 5, 9, 13, 22]);
+    assert_eq!(count_after(&[125, 17], 25).unwrap(), 55312);
+    assert!(count_after(&[125, 17], 500).is_err());
+    // Seventeen digits, times 2024, is too big for a u64.
+    assert_eq!(blink_all([10000000000000000]), Err(Overflow));
+    assert!(count_after(&[10000000000000000], 1).is_err());
+}
+
+#[test]
+fn test_other_rules() {
+    // Without splitting, every stone stays one stone.
+    let no_split: &[&dyn Rule] = &[&Replace { from: 0, to: 1 }, &Multiply(3)];
+    assert_eq!(count_after_with(no_split, &[0, 5, 7], 30).unwrap(), 3);
+    // Splitting anything of two digits or more, doubling the rest.
+    let split_all: &[&dyn Rule] = &[&SplitHalves { when: |_| true }, &Multiply(2)];
+    assert_eq!(blink_with(split_all, 1234), Ok(vec![12, 34]));
+    assert_eq!(blink_with(split_all, 7), Ok(vec![14]));
+    assert_eq!(count_after_with(split_all, &[7], 3).unwrap(), 2);
+    // No rules leave the stones alone.
+    assert_eq!(blink_with(&[], 42), Ok(vec![42]));
+    assert_eq!(count_after_with(&[], &[1, 2], 10).unwrap(), 2);
+    // Tripling a single stone outgrows a u64 before the count does.
+    assert_eq!(blink_with(no_split, u64::MAX / 2), Err(Overflow));
+    assert!(count_after_with(no_split, &[7], 45).is_err());
+}
+
+/// A stone's number, or the count of stones, got too big to keep.
+#[derive(Debug, PartialEq)]
+pub struct Overflow;
+
+/// One of the ways a stone changes when the stones blink.
+pub trait Rule {
+    /// What the stone numbered `stone` becomes, or None if this rule
+    /// doesn't apply to it.
+    fn apply(&self, stone: u64) -> Result<Option<Vec<u64>>, Overflow>;
+}
+
+/// A stone numbered `from` becomes one numbered `to`.
+pub struct Replace {
+    pub from: u64,
+    pub to: u64,
+}
+
+impl Rule for Replace {
+    fn apply(&self, stone: u64) -> Result<Option<Vec<u64>>, Overflow> {
+        Ok((stone == self.from).then(|| vec![self.to]))
+    }
+}
+
+/// A stone of two or more digits for which `when` holds splits into the
+/// left and right halves of its digits.
+pub struct SplitHalves {
+    pub when: fn(u64) -> bool,
+}
+
+impl Rule for SplitHalves {
+    fn apply(&self, stone: u64) -> Result<Option<Vec<u64>>, Overflow> {
+        Ok((stone >= 10 && (self.when)(stone)).then(|| split_halves(stone)))
+    }
+}
+
+/// Any stone's number is multiplied by this.
+pub struct Multiply(pub u64);
+
+impl Rule for Multiply {
+    fn apply(&self, stone: u64) -> Result<Option<Vec<u64>>, Overflow> {
+        let product = stone.checked_mul(self.0).ok_or(Overflow)?;
+        Ok(Some(vec![product]))
+    }
+}
+
+/// The puzzle's rules, in order: the first that applies wins.
+pub const PUZZLE_RULES: &[&dyn Rule] = &[
+    &Replace { from: 0, to: 1 },
+    &SplitHalves { when: even_digits },
+    &Multiply(2024),
+];
+
+/// What `number` becomes under the first of `rules` that applies to it.
+/// A stone no rule applies to stays as it is.
+fn blink_with(rules: &[&dyn Rule], number: u64) -> Result<Vec<u64>, Overflow> {
+    for rule in rules {
+        if let Some(stones) = rule.apply(number)? {
+            return Ok(stones);
+        }
+    }
+    Ok(vec![number])
+}
+
+fn blink(number: u64) -> Result<Vec<u64>, Overflow> {
+    blink_with(PUZZLE_RULES, number)
+}
+
+/// Whether `number` is written with an even number of digits.
+pub fn even_digits(number: u64) -> bool {
+    (number.checked_ilog10().unwrap_or(0) + 1).is_multiple_of(2)
+}
+
+fn split_halves(number: u64) -> Vec<u64> {
+    let digits = number.ilog10() + 1;
+    let modulo = 10u64.pow(digits / 2);
+    vec![number / modulo, number % modulo]
+}
+
+fn histogram(numbers: impl IntoIterator<Item = u64>) -> FxHashMap<u64, u128> {
+    let mut map = FxHashMap::default();
+    for n in numbers {
+        *map.entry(n).or_default() += 1;
+    }
+    map
+}
+
+/// One blink across the counts under `rules`, unless a stone's number or
+/// a count no longer fits.
+fn blink_all_counting(
+    rules: &[&dyn Rule],
+    numbers: FxHashMap<u64, u128>,
+) -> Result<FxHashMap<u64, u128>, Overflow> {
+    let mut map: FxHashMap<u64, u128> = FxHashMap::default();
+    for (n, factor) in numbers {
+        for v in blink_with(rules, n)? {
+            let count = map.entry(v).or_default();
+            *count = count.checked_add(factor).ok_or(Overflow)?;
+        }
+    }
+    Ok(map)
+}
+
+fn too_many(blinks: usize) -> AdventError {
+    AdventError::Unsupported(format!(
+        "Too many stones, or too big, to count after {} blinks",
+        blinks
+    ))
+}
+
+pub fn parse_stones(input: &str) -> Result<Vec<u64>, AdventError> {
+    Ok(parse(input).collect::<Result<Vec<_>, _>>()?)
+}
+
+/// How many stones there are after `blinks` blinks, counting stones by
+/// the number engraved on them, since stones with the same number go on
+/// to do the same thing.  The count roughly doubles every other blink, so
+/// even a u128 runs out after a couple of hundred.
+pub fn count_after(stones: &[u64], blinks: usize) -> Result<u128, AdventError> {
+    count_after_with(PUZZLE_RULES, stones, blinks)
+}
+
+/// Like `count_after`, but with the stones changing by `rules` rather
+/// than the puzzle's.
+pub fn count_after_with(
+    rules: &[&dyn Rule],
+    stones: &[u64],
+    blinks: usize,
+) -> Result<u128, AdventError> {
+    let mut counts = histogram(stones.iter().copied());
+    for blink in 0..blinks {
+        counts = blink_all_counting(rules, counts).map_err(|_| too_many(blink + 1))?;
+    }
+    counts
+        .values()
+        .try_fold(0u128, |total, &count| total.checked_add(count))
+        .ok_or_else(|| too_many(blinks))
+}
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    fn title(&self) -> &'static str {
+        "Plutonian Pebbles"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Count stones by engraved number rather than simulating each stone."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        Ok(count_after(&parse_stones(input)?, 25)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        Ok(count_after(&parse_stones(input)?, 75)?.to_string())
+    }
+}
+
+pub const PART1_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "counting map",
+        run: |input| Day11.part1(input),
+    },
+    Variant {
+        name: "expand list",
+        run: |input| {
+            let mut values: Vec<u64> = parse(input).collect::<Result<Vec<_>, _>>()?;
+            for blink in 0..25 {
+                values = blink_all(values).map_err(|_| too_many(blink + 1))?;
+            }
+            Ok(values.len().to_string())
+        },
+    },
+];
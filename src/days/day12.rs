@@ -0,0 +1,372 @@
+use crate::graph::DisjointSet;
+use crate::variants::Variant;
+use crate::{AdventError, Grid, Solver, DIRECTIONS};
+
+struct Plot<T> {
+    grid: Grid<T>,
+}
+
+#[derive(Debug)]
+struct Region<T> {
+    name: T,
+    indices: Vec<usize>,
+}
+
+impl Plot<char> {
+    fn new(s: &str) -> Result<Self, String> {
+        Ok(Plot {
+            grid: Grid::parse_chars(s, |ch| ch)?,
+        })
+    }
+}
+
+impl<T: PartialEq + Copy> Plot<T> {
+    fn collect_regions(&self) -> Vec<Region<T>> {
+        self.grid
+            .components(|a, b| a == b)
+            .members
+            .into_iter()
+            .map(|indices| Region {
+                name: self.grid[indices[0]],
+                indices,
+            })
+            .collect()
+    }
+
+    /// Like `collect_regions`, but joining each tile to the same plants
+    /// to its right and below in one pass, then gathering tiles by the
+    /// set they ended up in.
+    fn collect_regions_union_find(&self) -> Vec<Region<T>> {
+        let tiles = &self.grid.tiles;
+        let mut sets = DisjointSet::new(self.grid.len());
+        for index in 0..self.grid.len() {
+            for next in [tiles.right(index), tiles.down(index)]
+                .into_iter()
+                .flatten()
+            {
+                if self.grid[index] == self.grid[next] {
+                    sets.union(index, next);
+                }
+            }
+        }
+
+        const UNNUMBERED: usize = usize::MAX;
+        let mut region_of_root = vec![UNNUMBERED; self.grid.len()];
+        let mut regions: Vec<Region<T>> = Vec::new();
+        for index in 0..self.grid.len() {
+            let root = sets.find(index);
+            if region_of_root[root] == UNNUMBERED {
+                region_of_root[root] = regions.len();
+                regions.push(Region {
+                    name: self.grid[index],
+                    indices: Vec::new(),
+                });
+            }
+            regions[region_of_root[root]].indices.push(index);
+        }
+        regions
+    }
+
+    fn perimeter(&self, region: &Region<T>) -> usize {
+        // Every side of a tile not shared with another tile of the region,
+        // whether it faces another region or the edge of the map.
+        region
+            .indices
+            .iter()
+            .map(|&idx| {
+                4 - self
+                    .grid
+                    .neighbors(idx)
+                    .filter(|&i| self.grid[i] == self.grid[idx])
+                    .count()
+            })
+            .sum()
+    }
+
+    /// A region has as many sides as corners, so count those: looking
+    /// each way from a tile and then a quarter turn clockwise, it's an
+    /// outside corner if neither neighbor is in the region, and an inside
+    /// one if both are but the tile between them isn't.
+    fn sides(&self, region: &Region<T>) -> usize {
+        let tiles = &self.grid.tiles;
+        let same =
+            |other: Option<usize>| other.is_some_and(|other| self.grid[other] == region.name);
+        region
+            .indices
+            .iter()
+            .map(|&index| {
+                DIRECTIONS
+                    .into_iter()
+                    .filter(|&dir| {
+                        let turned = dir.clock();
+                        let ahead = tiles.dir_to(index, dir);
+                        let beside = tiles.dir_to(index, turned);
+                        match (same(ahead), same(beside)) {
+                            (false, false) => true,
+                            (true, true) => {
+                                let diagonal = ahead.and_then(|ahead| tiles.dir_to(ahead, turned));
+                                !same(diagonal)
+                            }
+                            _ => false,
+                        }
+                    })
+                    .count()
+            })
+            .sum()
+    }
+}
+
+impl<T> Region<T> {
+    fn area(&self) -> usize {
+        self.indices.len()
+    }
+}
+
+/// What fencing every region costs, at its area times `fence`.
+fn price(
+    plot: &Plot<char>,
+    regions: Vec<Region<char>>,
+    fence: fn(&Plot<char>, &Region<char>) -> usize,
+) -> usize {
+    regions
+        .into_iter()
+        .map(|region| region.area() * fence(plot, &region))
+        .sum()
+}
+
+fn part_1(plot: &Plot<char>) -> usize {
+    price(plot, plot.collect_regions(), Plot::perimeter)
+}
+
+fn part_2(plot: &Plot<char>) -> usize {
+    price(plot, plot.collect_regions(), Plot::sides)
+}
+
+pub const PART1_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "flood fill",
+        run: |input| Ok(part_1(&Plot::new(input)?).to_string()),
+    },
+    Variant {
+        name: "union-find",
+        run: |input| {
+            let plot = Plot::new(input)?;
+            let regions = plot.collect_regions_union_find();
+            Ok(price(&plot, regions, Plot::perimeter).to_string())
+        },
+    },
+];
+
+pub const PART2_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "flood fill",
+        run: |input| Ok(part_2(&Plot::new(input)?).to_string()),
+    },
+    Variant {
+        name: "union-find",
+        run: |input| {
+            let plot = Plot::new(input)?;
+            let regions = plot.collect_regions_union_find();
+            Ok(price(&plot, regions, Plot::sides).to_string())
+        },
+    },
+];
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    fn title(&self) -> &'static str {
+        "Garden Groups"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Flood fill the regions; for part 2, count each region's corners, since it has as many sides."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        Ok(part_1(&Plot::new(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        Ok(part_2(&Plot::new(input)?).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_collect_regions() -> Result<()> {
+        let data = "
+AAAA
+BBCD
+BBCC
+EEEC
+";
+        let plot = Plot::new(data).into_test_result()?;
+        let regions = plot.collect_regions();
+        verify_that!(
+            regions,
+            unordered_elements_are![
+                matches_pattern!(Region {
+                    name: eq(&'A'),
+                    indices: unordered_elements_are![&0, &1, &2, &3],
+                }),
+                matches_pattern!(Region {
+                    name: eq(&'B'),
+                    indices: unordered_elements_are![&4, &5, &8, &9],
+                }),
+                matches_pattern!(Region {
+                    name: eq(&'C'),
+                    indices: unordered_elements_are![&6, &10, &11, &15],
+                }),
+                matches_pattern!(Region {
+                    name: eq(&'D'),
+                    indices: unordered_elements_are![&7],
+                }),
+                matches_pattern!(Region {
+                    name: eq(&'E'),
+                    indices: unordered_elements_are![&12, &13, &14],
+                }),
+            ]
+        )?;
+
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_collect_regions_union_find() -> Result<()> {
+        let data = "
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+";
+        let plot = Plot::new(data).into_test_result()?;
+        let sorted = |regions: Vec<Region<char>>| {
+            let mut regions: Vec<(char, Vec<usize>)> = regions
+                .into_iter()
+                .map(|mut region| {
+                    region.indices.sort();
+                    (region.name, region.indices)
+                })
+                .collect();
+            regions.sort();
+            regions
+        };
+        let regions = sorted(plot.collect_regions_union_find());
+        verify_that!(regions.len(), eq(11))?;
+        verify_that!(regions, eq(&sorted(plot.collect_regions())))
+    }
+
+    #[gtest]
+    fn test_perimeters() -> Result<()> {
+        let data = "
+AAAA
+BBCD
+BBCC
+EEEC
+";
+        let plot = Plot::new(data).into_test_result()?;
+        let regions = plot.collect_regions();
+        verify_that!(
+            regions
+                .into_iter()
+                .map(|region| (region.name, plot.perimeter(&region)))
+                .collect::<Vec<_>>(),
+            { &('A', 10), &('B', 8), &('C', 10), &('D', 4), &('E', 8) }
+        )
+    }
+
+    #[gtest]
+    fn test_part_1() -> Result<()> {
+        let data = "
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+";
+        let plot = Plot::new(data).into_test_result()?;
+        verify_that!(part_1(&plot), eq(1930))
+    }
+
+    #[gtest]
+    fn test_sides() -> Result<()> {
+        let data = "
+AAAA
+BBCD
+BBCC
+EEEC
+";
+        let plot = Plot::new(data).into_test_result()?;
+        let regions = plot.collect_regions();
+        verify_that!(
+            regions
+                .into_iter()
+                .map(|region| (region.name, plot.sides(&region)))
+                .collect::<Vec<_>>(),
+            { &('A', 4), &('B', 4), &('C', 8), &('D', 4), &('E', 4) }
+        )
+    }
+
+    #[gtest]
+    fn test_sides_around_holes() -> Result<()> {
+        let data = "
+OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO
+";
+        let plot = Plot::new(data).into_test_result()?;
+        let regions = plot.collect_regions();
+        verify_that!(
+            regions
+                .iter()
+                .map(|region| (region.name, plot.sides(region)))
+                .collect::<Vec<_>>(),
+            { &('O', 20), &('X', 4), &('X', 4), &('X', 4), &('X', 4) }
+        )?;
+        verify_that!(part_2(&plot), eq(436))
+    }
+
+    #[gtest]
+    fn test_part_2() -> Result<()> {
+        let data = "
+AAAA
+BBCD
+BBCC
+EEEC
+";
+        let plot = Plot::new(data).into_test_result()?;
+        verify_that!(part_2(&plot), eq(80))
+    }
+
+    #[gtest]
+    fn test_part_2_intermediate() -> Result<()> {
+        let data = "
+AAAAAA
+AAABBA
+AAABBA
+ABBAAA
+ABBAAA
+AAAAAA
+";
+        let plot = Plot::new(data).into_test_result()?;
+        verify_that!(part_2(&plot), eq(368))
+    }
+}
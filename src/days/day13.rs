@@ -0,0 +1,480 @@
+#![allow(clippy::comparison_chain)]
+
+use crate::matrix::Matrix;
+use crate::number::egcd;
+use crate::search::dijkstra;
+use crate::variants::Variant;
+use crate::{AdventError, Solver, Vec2};
+use std::fmt::Write;
+
+/// Returns the minimal number of tokens needed, assuming it takes three
+/// tokens for an `a`, and one token for a `b`.
+///
+/// Idea: use Dijkstra's search, with two possible actions in the state space:
+/// * press button a once
+/// * press button b repeatedly directly to the prize
+///
+/// The asymmetry here is intentional, to reduce the size of the state
+/// space: we want to treat the sequence `[a pressed, b pressed, a
+/// pressed]` the same as `[a pressed, a pressed, b pressed]`.  So we
+/// design the possible actions so that we keep a canonical sequence,
+/// given the order independence between the button presses.
+fn dijkstra_solver(a: &Vec2, b: &Vec2, prize: &Vec2) -> Option<i64> {
+    let actions = |&point: &Vec2| {
+        // Action 1: press A.
+        let point_after_a = point + a;
+        let press_a = (point_after_a.0 <= prize.0 && point_after_a.1 <= prize.1)
+            .then_some((point_after_a, 3));
+
+        // Action 2: press B repeatedly if that can get us to the
+        // prize directly.  Use divisibility.
+        let delta = *prize - point;
+        let press_b =
+            (delta.0 % b.0 == 0 && delta.1 % b.1 == 0 && (delta.0 / b.0) == (delta.1 / b.1))
+                .then_some((*prize, delta.0 / b.0));
+
+        press_a.into_iter().chain(press_b)
+    };
+    let (tokens, _) = dijkstra(Vec2(0, 0), actions, |point| point == prize)?;
+    Some(tokens)
+}
+
+/// We want to find m, n such that for given points A, B, and P,
+///
+/// ```text
+///     m * [a0, a1] + n * [b0, b1] = [p0, p1]
+/// ```
+///
+/// If we write out the math, solutions are:
+///
+/// ```text
+///    m = (b0 * p1 - b1 * p0) / (a1 * b0 - a0 * b1)
+///    n = (a1 * p0 - a0 * p1) / (a1 * b0 - a0 * b1)
+/// ```
+fn linear_algebra_solver(a: &Vec2, b: &Vec2, p: &Vec2) -> Option<i64> {
+    let mut divisor = (a.1 * b.0).checked_sub(a.0 * b.1).expect("underflow");
+    let mut sign = 1;
+    if divisor == 0 {
+        return collinear_solver(a, b, p);
+    } else if divisor < 0 {
+        divisor = -divisor;
+        sign = -1;
+    }
+
+    let m_numerator = sign * (b.0 * p.1 - b.1 * p.0);
+    let n_numerator = sign * (a.1 * p.0 - a.0 * p.1);
+
+    if m_numerator % divisor == 0 && n_numerator % divisor == 0 {
+        Some((3 * m_numerator + n_numerator) / divisor)
+    } else {
+        // We give up on non-integer solutions, as that means there's
+        // no way to reach the prize.
+        None
+    }
+}
+
+/// The same system as `linear_algebra_solver`, by exact elimination
+/// instead of hand-derived formulas.  The same code would solve a
+/// machine with more buttons than axes' worth of unknowns, given as many
+/// equations.
+fn elimination_solver(a: &Vec2, b: &Vec2, p: &Vec2) -> Option<i64> {
+    let system = Matrix::from_rows(&[[a.0, b.0], [a.1, b.1]]);
+    let Some(presses) = system.solve(&[p.0, p.1]) else {
+        return collinear_solver(a, b, p);
+    };
+    let m = presses[0].to_integer()?;
+    let n = presses[1].to_integer()?;
+    (m >= 0 && n >= 0).then(|| (3 * m + n) as i64)
+}
+
+/// The cheapest presses when `a` and `b` point the same way, so there are
+/// many ways to reach the prize or none.  Along one axis, `m a + n b = p`
+/// is a linear Diophantine equation: its solutions are one particular
+/// solution, from the extended Euclidean algorithm, shifted by whole
+/// multiples of `(b, -a) / gcd(a, b)`.  The cost is linear in the shift,
+/// so the cheapest is at one end of the range where both counts are
+/// nonnegative.
+/// https://en.wikipedia.org/wiki/Diophantine_equation#One_equation
+fn collinear_solver(a: &Vec2, b: &Vec2, p: &Vec2) -> Option<i64> {
+    // The prize has to be on the same line.
+    if a.0 * p.1 != a.1 * p.0 || b.0 * p.1 != b.1 * p.0 {
+        return None;
+    }
+    let (a, b, p) = if a.0 != 0 || b.0 != 0 {
+        (a.0, b.0, p.0)
+    } else {
+        (a.1, b.1, p.1)
+    };
+    let cost = |m: i64, n: i64| (m >= 0 && n >= 0).then_some(3 * m + n);
+    match (a, b) {
+        (0, 0) => return (p == 0).then_some(0),
+        (0, b) => return (p % b == 0).then(|| cost(0, p / b)).flatten(),
+        (a, 0) => return (p % a == 0).then(|| cost(p / a, 0)).flatten(),
+        _ => {}
+    }
+
+    let (g, x, y) = egcd(a, b);
+    if p % g != 0 {
+        return None;
+    }
+    let (m0, n0) = (x * (p / g), y * (p / g));
+    let (m_step, n_step) = (b / g, a / g);
+    // m0 + k m_step >= 0 and n0 - k n_step >= 0.
+    let k_min = -m0.div_euclid(m_step);
+    let k_max = n0.div_euclid(n_step);
+    [k_min, k_max]
+        .into_iter()
+        .filter_map(|k| cost(m0 + k * m_step, n0 - k * n_step))
+        .min()
+}
+
+/// Walks through what `linear_algebra_solver` does for one claw machine,
+/// using Cramer's rule on the 2x2 system, and returns the steps as text.
+fn explain_linear_algebra(a: &Vec2, b: &Vec2, p: &Vec2) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "System:");
+    let _ = writeln!(out, "  {} m + {} n = {}", a.0, b.0, p.0);
+    let _ = writeln!(out, "  {} m + {} n = {}", a.1, b.1, p.1);
+
+    let det = a.0 * b.1 - b.0 * a.1;
+    let _ = writeln!(
+        out,
+        "Determinant: {} * {} - {} * {} = {}",
+        a.0, b.1, b.0, a.1, det
+    );
+    if det == 0 {
+        let _ = writeln!(out, "Buttons are collinear: no unique solution.");
+        return out;
+    }
+
+    let m_numerator = p.0 * b.1 - b.0 * p.1;
+    let n_numerator = a.0 * p.1 - p.0 * a.1;
+    let _ = writeln!(
+        out,
+        "m = ({} * {} - {} * {}) / {} = {} / {}",
+        p.0, b.1, b.0, p.1, det, m_numerator, det
+    );
+    let _ = writeln!(
+        out,
+        "n = ({} * {} - {} * {}) / {} = {} / {}",
+        a.0, p.1, p.0, a.1, det, n_numerator, det
+    );
+    if m_numerator % det == 0 && n_numerator % det == 0 {
+        let (m, n) = (m_numerator / det, n_numerator / det);
+        let _ = writeln!(out, "(m, n) = ({}, {})", m, n);
+        let _ = writeln!(out, "Cost: 3 * {} + {} = {}", m, n, 3 * m + n);
+    } else {
+        let _ = writeln!(out, "Non-integer solution: prize is unreachable.");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use indoc::indoc;
+
+    #[gtest]
+    fn test_dijkstra_solver_small() -> Result<()> {
+        verify_that!(
+            dijkstra_solver(&Vec2(94, 34), &Vec2(22, 67), &Vec2(8400, 5400)),
+            some(eq(280))
+        )?;
+
+        verify_that!(
+            dijkstra_solver(&Vec2(26, 66), &Vec2(67, 21), &Vec2(12748, 12176)),
+            none()
+        )?;
+
+        verify_that!(
+            dijkstra_solver(&Vec2(17, 86), &Vec2(84, 37), &Vec2(7870, 6450)),
+            some(eq(200))
+        )?;
+
+        verify_that!(
+            dijkstra_solver(&Vec2(64, 23), &Vec2(27, 71), &Vec2(18641, 10279)),
+            none()
+        )?;
+
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_linear_algebra_solver_small() -> Result<()> {
+        verify_that!(
+            linear_algebra_solver(&Vec2(94, 34), &Vec2(22, 67), &Vec2(8400, 5400)),
+            some(eq(280))
+        )?;
+
+        verify_that!(
+            linear_algebra_solver(&Vec2(26, 66), &Vec2(67, 21), &Vec2(12748, 12176)),
+            none()
+        )?;
+
+        verify_that!(
+            linear_algebra_solver(&Vec2(17, 86), &Vec2(84, 37), &Vec2(7870, 6450)),
+            some(eq(200))
+        )?;
+
+        verify_that!(
+            linear_algebra_solver(&Vec2(64, 23), &Vec2(27, 71), &Vec2(18641, 10279)),
+            none()
+        )?;
+
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_elimination_agrees_with_linear_algebra() -> Result<()> {
+        let claws = [
+            (Vec2(94, 34), Vec2(22, 67), Vec2(8400, 5400)),
+            (Vec2(26, 66), Vec2(67, 21), Vec2(12748, 12176)),
+            (Vec2(17, 86), Vec2(84, 37), Vec2(7870, 6450)),
+            (Vec2(64, 23), Vec2(27, 71), Vec2(18641, 10279)),
+            (Vec2(8, 4), Vec2(2, 1), Vec2(20, 10)),
+            (
+                Vec2(94, 34),
+                Vec2(22, 67),
+                Vec2(10000000008400, 10000000005400),
+            ),
+            (
+                Vec2(26, 66),
+                Vec2(67, 21),
+                Vec2(10000000012748, 10000000012176),
+            ),
+        ];
+        for (a, b, p) in claws {
+            verify_that!(
+                elimination_solver(&a, &b, &p),
+                eq(linear_algebra_solver(&a, &b, &p))
+            )?;
+        }
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_linear_algebra_solver_collinear() -> Result<()> {
+        // Pressing b four times beats any mix with a.
+        verify_that!(
+            linear_algebra_solver(&Vec2(2, 2), &Vec2(3, 3), &Vec2(12, 12)),
+            some(eq(4))
+        )?;
+        // Here a goes four times as far as b for three tokens, so m = 2,
+        // n = 2 beats m = 0, n = 10.
+        verify_that!(
+            linear_algebra_solver(&Vec2(8, 4), &Vec2(2, 1), &Vec2(20, 10)),
+            some(eq(8))
+        )?;
+        verify_that!(
+            linear_algebra_solver(&Vec2(6, 3), &Vec2(4, 2), &Vec2(14, 8)),
+            none()
+        )?;
+        verify_that!(
+            linear_algebra_solver(&Vec2(6, 3), &Vec2(4, 2), &Vec2(15, 15)),
+            none()
+        )?;
+        verify_that!(
+            linear_algebra_solver(&Vec2(4, 2), &Vec2(6, 3), &Vec2(2, 1)),
+            none()
+        )
+    }
+
+    #[gtest]
+    fn test_explain_linear_algebra() -> Result<()> {
+        verify_that!(
+            explain_linear_algebra(&Vec2(94, 34), &Vec2(22, 67), &Vec2(8400, 5400)),
+            eq(indoc! {"
+                System:
+                  94 m + 22 n = 8400
+                  34 m + 67 n = 5400
+                Determinant: 94 * 67 - 22 * 34 = 5550
+                m = (8400 * 67 - 22 * 5400) / 5550 = 444000 / 5550
+                n = (94 * 5400 - 8400 * 34) / 5550 = 222000 / 5550
+                (m, n) = (80, 40)
+                Cost: 3 * 80 + 40 = 280
+            "})
+        )?;
+
+        verify_that!(
+            explain_linear_algebra(&Vec2(26, 66), &Vec2(67, 21), &Vec2(12748, 12176)),
+            ends_with("Non-integer solution: prize is unreachable.\n")
+        )
+    }
+}
+
+mod parser {
+    use super::*;
+    use nom::bytes::complete::tag;
+    use nom::character::complete::{alpha1, i64, line_ending};
+    use nom::multi::{many1, separated_list0};
+    use nom::IResult;
+
+    pub fn parse_button(input: &str) -> IResult<&str, (&str, Vec2)> {
+        let (input, _) = tag("Button ")(input)?;
+        // eat A or B
+        let (input, name) = alpha1(input)?;
+        let (input, _) = tag(": X+")(input)?;
+        let (input, x) = i64(input)?;
+        let (input, _) = tag(", Y+")(input)?;
+        let (input, y) = i64(input)?;
+
+        Ok((input, (name, Vec2(x, y))))
+    }
+
+    pub fn parse_prize(input: &str) -> IResult<&str, Vec2> {
+        let (input, _) = tag("Prize: X=")(input)?;
+        let (input, x) = i64(input)?;
+        let (input, _) = tag(", Y=")(input)?;
+        let (input, y) = i64(input)?;
+        Ok((input, Vec2(x, y)))
+    }
+
+    pub fn parse_claw(input: &str) -> IResult<&str, (Vec2, Vec2, Vec2)> {
+        let (input, (_, a)) = parse_button(input)?;
+        let (input, _) = line_ending(input)?;
+        let (input, (_, b)) = parse_button(input)?;
+        let (input, _) = line_ending(input)?;
+        let (input, prize) = parse_prize(input)?;
+        Ok((input, (a, b, prize)))
+    }
+
+    pub fn parse_all_claws(s: &str) -> IResult<&str, Vec<(Vec2, Vec2, Vec2)>> {
+        separated_list0(many1(line_ending), parse_claw)(s)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use googletest::prelude::*;
+
+        #[gtest]
+        fn test_parse_button() -> Result<()> {
+            let (_, button) = parse_button("Button A: X+21, Y+56")?;
+            verify_that!(button, eq(("A", Vec2(21, 56))))?;
+
+            let (_, button) = parse_button("Button B: X+59, Y+28")?;
+            verify_that!(button, eq(("B", Vec2(59, 28))))?;
+            Ok(())
+        }
+
+        #[gtest]
+        fn test_parse_prize() -> Result<()> {
+            let (_, prize) = parse_prize("Prize: X=3892, Y=3840")?;
+            verify_that!(prize, eq(Vec2(3892, 3840)))?;
+
+            Ok(())
+        }
+
+        #[gtest]
+        fn test_parse_claw() -> Result<()> {
+            let (_, (a, b, prize)) = parse_claw(
+                "\
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400",
+            )?;
+            verify_that!(a, eq(Vec2(94, 34)))?;
+            verify_that!(b, eq(Vec2(22, 67)))?;
+            verify_that!(prize, eq(Vec2(8400, 5400)))?;
+
+            Ok(())
+        }
+    }
+}
+
+fn part_1(
+    claws: &[(Vec2, Vec2, Vec2)],
+    solver: impl Fn(&Vec2, &Vec2, &Vec2) -> Option<i64>,
+) -> i64 {
+    claws
+        .iter()
+        .filter_map(|(a, b, prize)| solver(a, b, prize))
+        .sum()
+}
+
+fn part_2(
+    claws: &[(Vec2, Vec2, Vec2)],
+    solver: impl Fn(&Vec2, &Vec2, &Vec2) -> Option<i64>,
+) -> i64 {
+    claws
+        .iter()
+        .map(|(a, b, prize)| {
+            (
+                a,
+                b,
+                Vec2(prize.0 + 10000000000000, prize.1 + 10000000000000),
+            )
+        })
+        .filter_map(|(a, b, prize)| solver(a, b, &prize))
+        .sum()
+}
+
+/// Button A, button B, and the prize location.
+type Claw = (Vec2, Vec2, Vec2);
+
+fn parse(input: &str) -> Result<Vec<Claw>, AdventError> {
+    let (_, claws) = parser::parse_all_claws(input).map_err(|e| e.to_owned())?;
+    Ok(claws)
+}
+
+/// The `--explain` text for every claw machine in the input.
+pub fn explanation(input: &str) -> Result<String, AdventError> {
+    let mut out = String::new();
+    for (i, (a, b, prize)) in parse(input)?.iter().enumerate() {
+        let _ = writeln!(out, "Claw {}:", i + 1);
+        let _ = writeln!(out, "{}", explain_linear_algebra(a, b, prize));
+    }
+    Ok(out)
+}
+
+pub const PART1_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "linear algebra",
+        run: |input| Ok(part_1(&parse(input)?, linear_algebra_solver).to_string()),
+    },
+    Variant {
+        name: "elimination",
+        run: |input| Ok(part_1(&parse(input)?, elimination_solver).to_string()),
+    },
+    // Far too slow for part 2's distant prizes.
+    Variant {
+        name: "dijkstra",
+        run: |input| Ok(part_1(&parse(input)?, dijkstra_solver).to_string()),
+    },
+];
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    fn title(&self) -> &'static str {
+        "Claw Contraption"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Solve each machine's 2x2 linear system with Cramer's rule."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        Ok(part_1(&parse(input)?, linear_algebra_solver).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        // Essentially, we're trying to find naturals n1, n2 such that
+        //    n1 * A + n2 * B = prize
+        // and
+        //    Cost(n1, n2) = 3*n1 + n2 is minimized.
+        //
+        // Can we treat this algebraically as a calculus problem?
+        //
+        // We can express n2 in terms of n1 for each problem, because
+        //
+        // n1 * A + n2 * B = prize
+        // ==>  n2 * B = (prize - n1 * A)
+
+        // Cost(n1, n2) * B = 3 * n1 * B + n2 * B
+        // ==> Cost(n1) * B = 3 * n1 * B + (prize - n1 * A)
+
+        Ok(part_2(&parse(input)?, linear_algebra_solver).to_string())
+    }
+}
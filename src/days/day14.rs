@@ -0,0 +1,215 @@
+use crate::interval::Interval;
+use crate::number::lcm;
+use crate::{parse_points, AdventError, Grid, Render, Simulate, Solver, SparseGrid, Vec2};
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+struct Robot {
+    pos: Vec2,
+    vel: Vec2,
+}
+
+impl Robot {
+    fn simulate_movement(self, n: u32, width: i64, height: i64) -> Self {
+        Self {
+            pos: (self.pos + n as i64 * self.vel).rem_euclid(width, height),
+            vel: self.vel,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use indoc::indoc;
+    use insta::assert_snapshot;
+
+    #[gtest]
+    fn test_simulate_movement() -> Result<()> {
+        let robot = Robot {
+            pos: Vec2(2, 4),
+            vel: Vec2(2, -3),
+        };
+        verify_that!(robot.simulate_movement(5, 11, 7).pos, eq(Vec2(1, 3)))
+    }
+
+    #[gtest]
+    fn test_parse_robot() -> Result<()> {
+        verify_that!(
+            parse("p=0,4 v=3,-3"),
+            ok(eq(&vec![Robot {
+                pos: Vec2(0, 4),
+                vel: Vec2(3, -3)
+            }]))
+        )
+    }
+
+    #[gtest]
+    fn test_render_example() -> Result<()> {
+        let robots = parse(indoc! {"
+            p=0,4 v=3,-3
+            p=6,3 v=-1,-3
+            p=10,3 v=-1,2
+            p=2,0 v=2,-1
+            p=0,0 v=1,3
+            p=3,0 v=-2,-2
+            p=7,6 v=-1,-3
+            p=3,0 v=-1,-2
+            p=9,3 v=2,3
+            p=7,3 v=-1,2
+            p=2,4 v=2,-3
+            p=9,5 v=-3,-3
+        "})
+        .into_test_result()?;
+        let robots: Vec<Robot> = robots
+            .into_iter()
+            .map(|r| r.simulate_movement(100, 11, 7))
+            .collect();
+        assert_snapshot!(Floor {
+            robots: &robots,
+            width: 11,
+            height: 7,
+        }
+        .render());
+        Ok(())
+    }
+}
+
+fn part_1(robots: Vec<Robot>) -> u32 {
+    let robots: Vec<Robot> = robots
+        .into_iter()
+        .map(|r| r.simulate_movement(100, 101, 103))
+        .collect();
+    // Each side, less the middle line.
+    let halves = |len: i64| {
+        Interval::new(0, len)
+            .difference(&Interval::new(len / 2, len / 2 + 1))
+            .collect::<Vec<_>>()
+    };
+    let mut scores = Vec::new();
+    for cols in halves(101) {
+        for rows in halves(103) {
+            scores.push(
+                robots
+                    .iter()
+                    .filter(|r| cols.contains(r.pos.0) && rows.contains(r.pos.1))
+                    .count() as u32,
+            );
+        }
+    }
+    scores.into_iter().product()
+}
+
+/// The robots as seen from above.
+struct Floor<'a> {
+    robots: &'a [Robot],
+    width: usize,
+    height: usize,
+}
+
+impl Render for Floor<'_> {
+    fn render(&self) -> String {
+        let floor = Grid::from_fn(self.width, self.height, |_| '.');
+        floor.render_marked(
+            |&ch| ch,
+            self.robots
+                .iter()
+                .filter_map(|r| r.pos.to_index(&floor.tiles)),
+            '*',
+        )
+    }
+}
+
+/// Exploration to find some kind of interesting pattern: a long horizontal
+/// run of robots is a good sign that a picture has formed.
+fn is_possible_picture(robots: &[Robot]) -> bool {
+    let pos = |r: &Robot| (r.pos.0 as isize, r.pos.1 as isize);
+    let floor: SparseGrid<()> = robots.iter().map(|r| (pos(r), ())).collect();
+    robots
+        .iter()
+        .map(pos)
+        .any(|(x, y)| (1..13).all(|dx| floor.contains((x + dx, y))))
+}
+
+/// The robots moving around the lobby, a second per step.
+#[derive(Debug, Clone)]
+struct Lobby {
+    robots: Vec<Robot>,
+    width: i64,
+    height: i64,
+}
+
+impl Simulate for Lobby {
+    fn step(&mut self) {
+        for robot in &mut self.robots {
+            *robot = robot.simulate_movement(1, self.width, self.height);
+        }
+    }
+}
+
+/// Returns the first second at which the robots might be drawing a picture.
+/// Each robot is back where it started every `width` seconds across and
+/// every `height` seconds down, so after their lcm every frame repeats.
+fn part_2(robots: Vec<Robot>) -> Option<u32> {
+    let lobby = Lobby {
+        robots,
+        width: 101,
+        height: 103,
+    };
+    let period = lcm(lobby.width, lobby.height);
+    let second = lobby
+        .iterate()
+        .take(period as usize)
+        .position(|lobby| is_possible_picture(&lobby.robots))?;
+    log::debug!("Frame {} looks like a picture", second);
+    Some(second as u32)
+}
+
+fn parse(input: &str) -> Result<Vec<Robot>, String> {
+    Ok(parse_points(input)?
+        .into_iter()
+        .map(|[pos, vel]| Robot {
+            pos: pos.into(),
+            vel: vel.into(),
+        })
+        .collect())
+}
+
+/// The robots' picture at the part 2 answer, if they ever form one.
+pub fn picture(input: &str) -> Result<Option<String>, AdventError> {
+    let robots = parse(input)?;
+    Ok(part_2(robots.clone()).map(|n| {
+        let robots: Vec<Robot> = robots
+            .into_iter()
+            .map(|r| r.simulate_movement(n, 101, 103))
+            .collect();
+        Floor {
+            robots: &robots,
+            width: 101,
+            height: 103,
+        }
+        .render()
+    }))
+}
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    fn title(&self) -> &'static str {
+        "Restroom Redoubt"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Move robots in closed form for part 1; for part 2, step until a long horizontal run of robots appears."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        Ok(part_1(parse(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        Ok(part_2(parse(input)?)
+            .ok_or_else(|| AdventError::no_solution("No picture before the robots repeat"))?
+            .to_string())
+    }
+}
@@ -0,0 +1,551 @@
+use crate::{split_sections, AdventError, Direction, FromInput, Grid, Render, Solver, TileIndex};
+use std::collections::HashSet;
+
+#[derive(Debug, PartialEq)]
+enum Entity {
+    Empty,
+    Boulder(BoulderShape),
+    Wall,
+    Player,
+}
+
+#[derive(Debug, PartialEq)]
+enum BoulderShape {
+    Single,
+    WideLeft,
+    WideRight,
+}
+
+impl TryFrom<char> for Entity {
+    type Error = AdventError;
+    fn try_from(ch: char) -> Result<Self, AdventError> {
+        match ch {
+            '#' => Ok(Entity::Wall),
+            '@' => Ok(Entity::Player),
+            'O' => Ok(Entity::Boulder(BoulderShape::Single)),
+            '[' => Ok(Entity::Boulder(BoulderShape::WideLeft)),
+            ']' => Ok(Entity::Boulder(BoulderShape::WideRight)),
+            '.' => Ok(Entity::Empty),
+            _ => Err(AdventError::parse(format!(
+                "Unexpected map character {:?}",
+                ch
+            ))),
+        }
+    }
+}
+
+impl From<&Entity> for char {
+    fn from(entity: &Entity) -> Self {
+        match entity {
+            Entity::Wall => '#',
+            Entity::Player => '@',
+            Entity::Boulder(BoulderShape::Single) => 'O',
+            Entity::Boulder(BoulderShape::WideLeft) => '[',
+            Entity::Boulder(BoulderShape::WideRight) => ']',
+            Entity::Empty => '.',
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+struct Sokoban {
+    data: Vec<Entity>,
+    tiles: TileIndex,
+    player_pos: usize,
+}
+
+impl Sokoban {
+    fn forward(&mut self, dir: Direction) {
+        let mut to_move: Vec<usize> = vec![self.player_pos];
+        let mut border: HashSet<usize> = HashSet::new();
+        border.insert(self.player_pos);
+
+        loop {
+            // Find the next border (and if we fall off, fail)
+            let Some(next_border) = border
+                .iter()
+                .map(|pos| {
+                    self.tiles
+                        .dir_to(*pos, dir)
+                        .map(|pos| (pos, &self.data[pos]))
+                })
+                .collect::<Option<Vec<(usize, &Entity)>>>()
+            else {
+                return;
+            };
+
+            // Give up if any of them are walls.
+            if next_border
+                .iter()
+                .any(|(_, entity)| matches!(entity, Entity::Wall))
+            {
+                return;
+            }
+
+            // Push if all of them are empty
+            if next_border
+                .iter()
+                .all(|(_, entity)| matches!(entity, Entity::Empty))
+            {
+                for pos in to_move.into_iter().rev() {
+                    self.data.swap(pos, self.tiles.dir_to(pos, dir).unwrap());
+                }
+
+                self.player_pos = self.tiles.dir_to(self.player_pos, dir).unwrap();
+                return;
+            }
+
+            // Otherwise, set up the border with the boulders, and loop.
+            border = next_border
+                .iter()
+                .flat_map(|(pos, entity)| match entity {
+                    Entity::Empty => vec![],
+                    Entity::Boulder(shape) => {
+                        if dir.is_horizontal() {
+                            vec![*pos]
+                        } else {
+                            match shape {
+                                BoulderShape::Single => {
+                                    vec![*pos]
+                                }
+                                BoulderShape::WideLeft => {
+                                    vec![*pos, self.tiles.dir_to(*pos, Direction::Right).unwrap()]
+                                }
+                                BoulderShape::WideRight => {
+                                    vec![*pos, self.tiles.dir_to(*pos, Direction::Left).unwrap()]
+                                }
+                            }
+                        }
+                    }
+                    Entity::Wall => panic!("impossible"),
+                    Entity::Player => vec![*pos],
+                })
+                .collect();
+            to_move.extend(border.iter());
+        }
+    }
+
+    /// The map as characters.
+    fn grid(&self) -> Grid<char> {
+        Grid::from_fn(self.tiles.width, self.tiles.height, |index| {
+            char::from(&self.data[index])
+        })
+    }
+
+    fn score(&self) -> u32 {
+        self.data
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, entity)| match entity {
+                Entity::Boulder(BoulderShape::Single | BoulderShape::WideLeft) => {
+                    let (row, col) = self.tiles.to_rc(pos);
+                    Some(100 * row as u32 + col as u32)
+                }
+                _ => None,
+            })
+            .sum()
+    }
+}
+
+impl FromInput for Sokoban {
+    fn from_input(input: &str) -> Result<Self, AdventError> {
+        let chars = Grid::parse_chars(input, |ch| ch)?;
+        let data = chars
+            .iter()
+            .map(|&ch| Entity::try_from(ch))
+            .collect::<Result<Vec<_>, _>>()?;
+        let grid = Grid::new(data, chars.tiles);
+        let player_pos = grid
+            .position_of(&Entity::Player)
+            .ok_or_else(|| AdventError::parse("No player found in map"))?;
+        Ok(Self {
+            tiles: grid.tiles.clone(),
+            data: grid.into_vec(),
+            player_pos,
+        })
+    }
+}
+
+impl Render for Sokoban {
+    fn render(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl std::fmt::Display for Sokoban {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        if self.data[self.player_pos] != Entity::Player {
+            write!(
+                formatter,
+                "Warning!  player_pos inconsistency!  player_pos: {:?}",
+                self.player_pos
+            )?;
+        }
+
+        write!(formatter, "{}", self.grid().render())
+    }
+}
+
+fn parse_directions(s: &str) -> Result<Vec<Direction>, String> {
+    s.trim()
+        .chars()
+        .filter(|ch| !ch.is_ascii_whitespace())
+        .map(Direction::try_from)
+        .collect()
+}
+
+fn parse_part_1_problem(s: &str) -> Result<(Sokoban, Vec<Direction>), AdventError> {
+    let [map, directions] = split_sections(s, ["map", "directions"])?;
+    Ok((Sokoban::from_input(map)?, parse_directions(directions)?))
+}
+
+/// This handles the map expansion for part 2.
+fn widen_map(s: &str) -> String {
+    s.chars()
+        .flat_map(|ch| match ch {
+            '#' => vec!['#', '#'],
+            'O' => vec!['[', ']'],
+            '.' => vec!['.', '.'],
+            '@' => vec!['@', '.'],
+            _ => vec![ch],
+        })
+        .collect()
+}
+
+fn parse_part_2_problem(s: &str) -> Result<(Sokoban, Vec<Direction>), AdventError> {
+    let [map, directions] = split_sections(s, ["map", "directions"])?;
+    Ok((
+        Sokoban::from_input(&widen_map(map))?,
+        parse_directions(directions)?,
+    ))
+}
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    fn title(&self) -> &'static str {
+        "Warehouse Woes"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Simulate the robot pushing boxes, with double-width boxes for part 2."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        let (mut sokoban, directions) = parse_part_1_problem(input)?;
+        for direction in directions {
+            sokoban.forward(direction);
+        }
+        Ok(sokoban.score().to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        let (mut sokoban, directions) = parse_part_2_problem(input)?;
+        for direction in directions {
+            sokoban.forward(direction);
+        }
+        Ok(sokoban.score().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use indoc::indoc;
+    use insta::assert_snapshot;
+
+    #[gtest]
+    fn test_unexpected_map_character() -> Result<()> {
+        verify_that!(Sokoban::from_input("#@x#"), err(anything()))?;
+        verify_that!(Day15.part1("#@x#\n\n<"), err(anything()))?;
+        verify_that!(Day15.part2("#@x#\n\n<"), err(anything()))
+    }
+
+    #[gtest]
+    fn test_sokoban_parsing() -> Result<()> {
+        let data = "\
+	####
+        #.O#
+        #@.#
+        ####
+	";
+        use Entity as E;
+        verify_that!(
+            Sokoban::from_input(data)?,
+            eq(&Sokoban {
+                data: vec![
+                    E::Wall,
+                    E::Wall,
+                    E::Wall,
+                    E::Wall,
+                    E::Wall,
+                    E::Empty,
+                    E::Boulder(BoulderShape::Single),
+                    E::Wall,
+                    E::Wall,
+                    E::Player,
+                    E::Empty,
+                    E::Wall,
+                    E::Wall,
+                    E::Wall,
+                    E::Wall,
+                    E::Wall,
+                ],
+                player_pos: 9,
+                tiles: TileIndex {
+                    width: 4,
+                    height: 4
+                },
+            },)
+        )
+    }
+
+    #[gtest]
+    fn test_movement() -> Result<()> {
+        let mut board = Sokoban::from_input(indoc! {"
+	####
+        #@.#
+        #..#
+        ####
+	"})?;
+
+        board.forward(Direction::Right);
+
+        assert_snapshot!(board.render());
+
+        board.forward(Direction::Down);
+
+        assert_snapshot!(board.render());
+
+        board.forward(Direction::Left);
+
+        assert_snapshot!(board.render());
+
+        board.forward(Direction::Up);
+
+        assert_snapshot!(board.render());
+
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_movement_pushing() -> Result<()> {
+        let mut board = Sokoban::from_input(indoc! {"
+        ###########
+        #@O.O.#...#
+        #.........#
+        ###########
+	"})?;
+
+        board.forward(Direction::Right);
+
+        assert_snapshot!(board.render());
+
+        board.forward(Direction::Right);
+
+        assert_snapshot!(board.render());
+
+        board.forward(Direction::Right);
+
+        assert_snapshot!(board.render());
+
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_pushing_wide_diff() -> Result<()> {
+        let mut board = Sokoban::from_input(indoc! {"
+            ##############
+            ##..........##
+            ##...[][]...##
+            ##....[]....##
+            ##.....@....##
+            ##############
+        "})?;
+
+        let before = board.grid();
+        board.forward(Direction::Up);
+
+        assert_snapshot!(before.render_diff(&board.grid(), |&ch| ch));
+
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_pushing_wide() -> Result<()> {
+        let mut board = Sokoban::from_input(indoc! {"
+            ##############
+            ##......##..##
+            ##..........##
+            ##....[][]@.##
+            ##....[]....##
+            ##..........##
+            ##############
+            "
+        })?;
+
+        board.forward(Direction::Left);
+
+        assert_snapshot!(board.render());
+
+        board.forward(Direction::Down);
+        board.forward(Direction::Down);
+        board.forward(Direction::Left);
+        board.forward(Direction::Left);
+
+        assert_snapshot!(board.render());
+
+        board.forward(Direction::Up);
+
+        assert_snapshot!(board.render());
+
+        board.forward(Direction::Up);
+
+        assert_snapshot!(board.render());
+
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_small_example() -> Result<()> {
+        let data = indoc! {"
+########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<
+"
+        };
+        let (mut sokoban, directions) = parse_part_1_problem(data)?;
+        for direction in directions {
+            sokoban.forward(direction);
+        }
+
+        verify_that!(sokoban.score(), eq(2028))
+    }
+
+    #[gtest]
+    fn test_large_example() -> Result<()> {
+        let data = indoc! {"
+##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
+"
+        };
+        let (mut sokoban, directions) = parse_part_1_problem(data)?;
+        for direction in directions {
+            sokoban.forward(direction);
+        }
+
+        verify_that!(sokoban.score(), eq(10092))
+    }
+
+    #[gtest]
+    fn test_score_wide() -> Result<()> {
+        let board = Sokoban::from_input(indoc! {"
+        ##########
+        #@.[]....#
+        #........#
+        ##########
+"})?;
+        verify_that!(board.score(), eq(103))?;
+
+        let board = Sokoban::from_input(indoc! {"
+        ##########
+        #@..[]...#
+        #........#
+        ##########
+"})?;
+        verify_that!(board.score(), eq(104))?;
+
+        let board = Sokoban::from_input(indoc! {"
+        ##########
+        #@...[]..#
+        #........#
+        ##########
+"})?;
+        verify_that!(board.score(), eq(105))?;
+
+        let board = Sokoban::from_input(indoc! {"
+        ##########
+        #@.......#
+        #....[]..#
+        ##########
+"})?;
+        verify_that!(board.score(), eq(205))?;
+
+        let board = Sokoban::from_input(indoc! {"
+        ##########
+        #@.......#
+        #........#
+        #........#
+        #........#
+        #........#
+        #....[]..#
+        ##########
+"})?;
+        verify_that!(board.score(), eq(605))?;
+
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_large_example_scaled() -> Result<()> {
+        let data = indoc! {"
+##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
+"
+        };
+        let (mut sokoban, directions) = parse_part_2_problem(data)?;
+        for direction in directions {
+            sokoban.forward(direction);
+        }
+
+        assert_snapshot!(sokoban.render());
+
+        verify_that!(sokoban.score(), eq(9021))
+    }
+}
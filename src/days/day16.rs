@@ -0,0 +1,471 @@
+use crate::search::{dijkstra, small_weight_bfs};
+use crate::variants::Variant;
+use crate::{
+    AdventError, Agent, BitGrid, Direction, FromInput, FxHashMap, FxHashSet, Grid, Render, Solver,
+    TileIndex,
+};
+use std::cmp::Ordering;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+#[derive(Debug, PartialEq, Clone)]
+struct Maze {
+    collision_map: BitGrid, // we want this repr for cheap cloning.
+    tiles: TileIndex,
+    start: Agent,
+    goal: usize,
+}
+
+impl Maze {
+    /// Moves forward, unless a wall is in the way.
+    fn forward(&self, player: &Agent) -> Option<Agent> {
+        player
+            .forward(&self.tiles)
+            .filter(|p| !self.collision_map.contains(p.pos))
+    }
+}
+
+impl Render for Maze {
+    fn render(&self) -> String {
+        let mut result = String::new();
+        for pos in 0..self.tiles.width * self.tiles.height {
+            result.push(if self.collision_map.contains(pos) {
+                '#'
+            } else if pos == self.goal {
+                'E'
+            } else {
+                '.'
+            });
+            if self.tiles.col(pos) == self.tiles.width - 1 {
+                result.push('\n');
+            }
+        }
+        result
+    }
+}
+
+/// Find minimal score navigating the maze.
+fn search(maze: &Maze, start: &Agent) -> Option<u32> {
+    let (score, path) = dijkstra(
+        *start,
+        |player| {
+            maze.forward(player).map(|p| (p, 1)).into_iter().chain([
+                (player.turn_clock(), 1000),
+                (player.turn_counterclock(), 1000),
+            ])
+        },
+        |player| player.pos == maze.goal,
+    )?;
+    log::debug!(
+        "Reached goal at score {} in {} moves",
+        score,
+        path.len() - 1
+    );
+    Some(score)
+}
+
+/// The same as `search`, but with a bucket queue in place of the heap:
+/// moves and turns cost only 1 or 1000.
+fn search_buckets(maze: &Maze, start: &Agent) -> Option<u32> {
+    small_weight_bfs(*start, |player| {
+        maze.forward(player).map(|p| (p, 1)).into_iter().chain([
+            (player.turn_clock(), 1000),
+            (player.turn_counterclock(), 1000),
+        ])
+    })
+    .iter()
+    .filter(|(player, _)| player.pos == maze.goal)
+    .map(|(_, score)| score as u32)
+    .min()
+}
+
+/// Find number of unique titles finding the shortest path.
+fn search2(maze: &Maze, start: &Agent) -> Option<u32> {
+    // Do an initial search to bound how far we consider solutions.  I
+    // know we can do this in-place, but this seems simple enough.
+    let min_score = search(maze, start)?;
+
+    let mut visited: FxHashMap<Agent, u32> = FxHashMap::default();
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    struct AugmentedPlayerState {
+        player: Agent,
+        breadcrumb: FxHashSet<usize>,
+    }
+
+    impl Ord for AugmentedPlayerState {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.player.cmp(&other.player)
+        }
+    }
+
+    impl PartialOrd for AugmentedPlayerState {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    let mut heap: BinaryHeap<Reverse<(u32, AugmentedPlayerState)>> = BinaryHeap::new();
+    heap.push(Reverse((
+        0,
+        AugmentedPlayerState {
+            player: *start,
+            breadcrumb: [start.pos].into_iter().collect(),
+        },
+    )));
+
+    let mut solution_paths: FxHashSet<usize> = FxHashSet::default();
+
+    while let Some(Reverse((score, AugmentedPlayerState { player, breadcrumb }))) = heap.pop() {
+        if score > min_score {
+            continue;
+        }
+        // Check to see if we've been here before at shorter cost.
+        let visited_entry = visited.entry(player);
+        if *visited_entry.or_insert(u32::MAX) < score {
+            continue;
+        }
+        visited.insert(player, score);
+
+        if player.pos == maze.goal {
+            log::trace!("Best path found; heap has {} entries", heap.len());
+            solution_paths.extend(breadcrumb);
+            continue;
+        }
+
+        if let Some(p) = maze.forward(&player) {
+            let mut breadcrumb = breadcrumb.clone();
+            breadcrumb.insert(p.pos);
+            heap.push(Reverse((
+                score + 1,
+                AugmentedPlayerState {
+                    player: p,
+                    breadcrumb,
+                },
+            )));
+        }
+
+        let player_clock = player.turn_clock();
+        heap.push(Reverse((
+            score + 1000,
+            AugmentedPlayerState {
+                player: player_clock,
+                breadcrumb: breadcrumb.clone(),
+            },
+        )));
+
+        let player_counterclock = player.turn_counterclock();
+        heap.push(Reverse((
+            score + 1000,
+            AugmentedPlayerState {
+                player: player_counterclock,
+                breadcrumb,
+            },
+        )));
+    }
+
+    Some(solution_paths.len() as u32)
+}
+
+/// Reasoning artifacts behind the part 1 answer, for `--explain`.
+#[derive(Debug, PartialEq)]
+struct Explanation {
+    /// Number of distinct states popped off the heap before the goal.
+    expanded: usize,
+    /// Forward moves along one best path.
+    steps: u32,
+    /// Quarter turns along the same path.
+    turns: u32,
+    /// First state, walking from the start, where two best paths part ways.
+    divergence: Option<Agent>,
+}
+
+fn explain(maze: &Maze, start: &Agent) -> Option<Explanation> {
+    let mut heap: BinaryHeap<Reverse<(u32, Agent)>> = BinaryHeap::new();
+    let mut best: FxHashMap<Agent, u32> = FxHashMap::default();
+    let mut predecessors: FxHashMap<Agent, Vec<Agent>> = FxHashMap::default();
+    let mut expanded: FxHashSet<Agent> = FxHashSet::default();
+    let mut goals: Vec<Agent> = Vec::new();
+    let mut goal_score: Option<u32> = None;
+
+    best.insert(*start, 0);
+    heap.push(Reverse((0, *start)));
+    while let Some(Reverse((score, player))) = heap.pop() {
+        if goal_score.is_some_and(|goal_score| score > goal_score) {
+            break;
+        }
+        if !expanded.insert(player) {
+            continue;
+        }
+        if player.pos == maze.goal {
+            goal_score = Some(score);
+            goals.push(player);
+            continue;
+        }
+
+        let successors = maze
+            .forward(&player)
+            .map(|p| (score + 1, p))
+            .into_iter()
+            .chain([
+                (score + 1000, player.turn_clock()),
+                (score + 1000, player.turn_counterclock()),
+            ]);
+        for (next_score, next) in successors {
+            let entry = best.entry(next).or_insert(u32::MAX);
+            if next_score < *entry {
+                *entry = next_score;
+                predecessors.insert(next, vec![player]);
+                heap.push(Reverse((next_score, next)));
+            } else if next_score == *entry {
+                predecessors.entry(next).or_default().push(player);
+            }
+        }
+    }
+
+    // Walk back from the goal to count the moves on one best path.
+    let (mut steps, mut turns) = (0, 0);
+    let mut current = *goals.first()?;
+    while let Some(previous) = predecessors.get(&current).and_then(|ps| ps.first()) {
+        if previous.pos == current.pos {
+            turns += 1;
+        } else {
+            steps += 1;
+        }
+        current = *previous;
+    }
+
+    // Collect every state on some best path, remembering who leads where.
+    let mut successors: FxHashMap<Agent, FxHashSet<Agent>> = FxHashMap::default();
+    let mut to_visit = goals.clone();
+    let mut on_best_path: FxHashSet<Agent> = FxHashSet::default();
+    while let Some(state) = to_visit.pop() {
+        if !on_best_path.insert(state) {
+            continue;
+        }
+        for previous in predecessors.get(&state).into_iter().flatten() {
+            successors.entry(*previous).or_default().insert(state);
+            to_visit.push(*previous);
+        }
+    }
+
+    let mut divergence = None;
+    let mut current = *start;
+    while let Some(nexts) = successors.get(&current) {
+        if nexts.len() > 1 {
+            divergence = Some(current);
+            break;
+        }
+        current = *nexts.iter().next()?;
+    }
+
+    Some(Explanation {
+        expanded: expanded.len(),
+        steps,
+        turns,
+        divergence,
+    })
+}
+
+impl FromInput for Maze {
+    fn from_input(input: &str) -> Result<Self, AdventError> {
+        let chars = Grid::parse_chars(input, |ch| ch)?;
+        let pos = chars
+            .position_of(&'S')
+            .ok_or_else(|| AdventError::parse("No start"))?;
+        let goal = chars
+            .position_of(&'E')
+            .ok_or_else(|| AdventError::parse("No end"))?;
+        let mut walls = BitGrid::new(chars.tiles.clone());
+        for index in chars.find_all(|&ch| ch == '#') {
+            walls.set(index);
+        }
+
+        Ok(Maze {
+            collision_map: walls,
+            tiles: chars.tiles,
+            start: Agent {
+                pos,
+                dir: Direction::Right,
+            },
+            goal,
+        })
+    }
+}
+
+/// The `--explain` text: how the part 1 search reached its answer.
+pub fn explanation(input: &str) -> Result<String, AdventError> {
+    let maze = Maze::from_input(input)?;
+    let player = maze.start;
+    let Some(explanation) = explain(&maze, &player) else {
+        return Ok("No path to goal\n".into());
+    };
+
+    let mut out = format!("States expanded: {}\n", explanation.expanded);
+    out += &format!(
+        "Cost: {} steps + 1000 * {} turns = {}\n",
+        explanation.steps,
+        explanation.turns,
+        explanation.steps + 1000 * explanation.turns
+    );
+    out += &match explanation.divergence {
+        Some(Agent { pos, dir }) => {
+            let (row, col) = maze.tiles.to_rc(pos);
+            format!(
+                "Best paths first diverge at row {}, col {} facing {:?}\n",
+                row, col, dir
+            )
+        }
+        None => "Best path is unique\n".into(),
+    };
+    Ok(out)
+}
+
+pub const PART1_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "dijkstra",
+        run: |input| {
+            let maze = Maze::from_input(input)?;
+            let player = maze.start;
+            Ok(search(&maze, &player)
+                .ok_or_else(|| AdventError::no_solution("No path to goal"))?
+                .to_string())
+        },
+    },
+    Variant {
+        name: "bucket queue",
+        run: |input| {
+            let maze = Maze::from_input(input)?;
+            let player = maze.start;
+            Ok(search_buckets(&maze, &player)
+                .ok_or_else(|| AdventError::no_solution("No path to goal"))?
+                .to_string())
+        },
+    },
+];
+
+pub struct Day16;
+
+impl Solver for Day16 {
+    fn title(&self) -> &'static str {
+        "Reindeer Maze"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Dijkstra over (position, facing); part 2 keeps the tiles of every best path."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        let maze = Maze::from_input(input)?;
+        let player = maze.start;
+        Ok(search(&maze, &player)
+            .ok_or_else(|| AdventError::no_solution("No path to goal"))?
+            .to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        let maze = Maze::from_input(input)?;
+        let player = maze.start;
+        Ok(search2(&maze, &player)
+            .ok_or_else(|| AdventError::no_solution("No path to goal"))?
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use insta::assert_snapshot;
+
+    const DATA: &str = "
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############
+";
+
+    #[gtest]
+    fn test_parse() -> Result<()> {
+        let maze = Maze::from_input(
+            "
+###############
+#.......#....E#
+#S..#.....#...#
+###############
+",
+        )?;
+        let player = maze.start;
+        verify_that!(maze.tiles.height, eq(4))?;
+        verify_that!(maze.tiles.width, eq(15))?;
+        verify_that!(maze.goal, eq(28))?;
+        verify_that!(player.pos, eq(31))?;
+
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_render() -> Result<()> {
+        let maze = Maze::from_input(DATA)?;
+        assert_snapshot!(maze.render());
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_search() -> Result<()> {
+        let maze = Maze::from_input(DATA)?;
+        let player = maze.start;
+        verify_that!(search(&maze, &player), some(eq(7036)))?;
+        verify_that!(search_buckets(&maze, &player), some(eq(7036)))
+    }
+
+    #[gtest]
+    fn test_search2() -> Result<()> {
+        let maze = Maze::from_input(DATA)?;
+        let player = maze.start;
+        verify_that!(search2(&maze, &player), some(eq(45)))
+    }
+
+    #[gtest]
+    fn test_explain() -> Result<()> {
+        let maze = Maze::from_input(DATA)?;
+        let player = maze.start;
+        let explanation = explain(&maze, &player).unwrap();
+        verify_that!(explanation.steps, eq(36))?;
+        verify_that!(explanation.turns, eq(7))?;
+        verify_that!(explanation.divergence, some(anything()))?;
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_explain_unique_path() -> Result<()> {
+        let maze = Maze::from_input(
+            "
+#####
+#S.E#
+#####
+",
+        )?;
+        let player = maze.start;
+        verify_that!(
+            explain(&maze, &player),
+            some(eq(&Explanation {
+                expanded: 3,
+                steps: 2,
+                turns: 0,
+                divergence: None,
+            }))
+        )
+    }
+}
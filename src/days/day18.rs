@@ -0,0 +1,176 @@
+use crate::search::bfs;
+use crate::variants::Variant;
+use crate::{parse_points, partition_point, AdventError, BitGrid, Solver, TileIndex};
+
+/// The memory space, with the bytes that have fallen so far.
+struct Memory {
+    corrupted: BitGrid,
+}
+
+impl Memory {
+    fn new(width: u8, height: u8) -> Self {
+        Self {
+            corrupted: BitGrid::new(TileIndex {
+                width: width as usize,
+                height: height as usize,
+            }),
+        }
+    }
+
+    fn mark(&mut self, (x, y): (u8, u8)) {
+        let index = self.corrupted.tiles.from_rc(y as usize, x as usize);
+        self.corrupted.set(index);
+    }
+
+    fn step_count(&self) -> Option<u32> {
+        let tiles = &self.corrupted.tiles;
+        let exit = tiles.width * tiles.height - 1;
+        let distances = bfs(0, |&index| {
+            tiles
+                .neighbors4(index)
+                .map(|(next, _)| next)
+                .filter(|&next| !self.corrupted.contains(next))
+        });
+        distances.distance(&exit).map(|steps| steps as u32)
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<(u8, u8)>, String> {
+    Ok(parse_points(input)?.into_iter().map(|[xy]| xy).collect())
+}
+
+/// Finds the first byte that cuts off the exit by re-checking after every
+/// byte falls.
+fn first_blocker_linear(coords: &[(u8, u8)], size: u8) -> Option<(u8, u8)> {
+    let mut grid = Memory::new(size, size);
+    for c in coords {
+        grid.mark(*c);
+        if grid.step_count().is_none() {
+            return Some(*c);
+        }
+    }
+    None
+}
+
+/// Finds the same byte as `first_blocker_linear`, bisecting over how many
+/// bytes have fallen.
+fn first_blocker_binary(coords: &[(u8, u8)], size: u8) -> Option<(u8, u8)> {
+    // Other folks suggested using binary search, so let's try that approach too.
+    let idx = partition_point(coords.len(), |n| {
+        let mut grid = Memory::new(size, size);
+        for c in &coords[..=n] {
+            grid.mark(*c);
+        }
+        grid.step_count().is_some()
+    });
+    coords.get(idx).copied()
+}
+
+fn format_blocker(blocker: Option<(u8, u8)>) -> Result<String, AdventError> {
+    let (x, y) = blocker.ok_or_else(|| AdventError::no_solution("Exit never blocked"))?;
+    Ok(format!("{},{}", x, y))
+}
+
+pub const PART2_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "binary search",
+        run: |input| format_blocker(first_blocker_binary(&parse(input)?, 71)),
+    },
+    Variant {
+        name: "linear scan",
+        run: |input| format_blocker(first_blocker_linear(&parse(input)?, 71)),
+    },
+];
+
+pub struct Day18;
+
+impl Solver for Day18 {
+    fn title(&self) -> &'static str {
+        "RAM Run"
+    }
+
+    fn notes(&self) -> &'static str {
+        "BFS through the corrupted grid; binary search for the first byte that blocks the exit."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        let coords = parse(input)?;
+        let mut grid = Memory::new(71, 71);
+        for c in coords.iter().take(1024) {
+            grid.mark(*c);
+        }
+        Ok(grid
+            .step_count()
+            .ok_or_else(|| AdventError::no_solution("No path to exit"))?
+            .to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        format_blocker(first_blocker_binary(&parse(input)?, 71))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_step_count() -> Result<()> {
+        let data = "\
+5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+";
+        let coords = parse(data).into_test_result()?;
+        let mut grid = Memory::new(7, 7);
+        for c in coords {
+            grid.mark(c);
+        }
+        verify_that!(grid.step_count(), some(eq(22)))
+    }
+
+    #[gtest]
+    fn test_first_blocker() -> Result<()> {
+        let data = "\
+5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0
+";
+        let coords = parse(data).into_test_result()?;
+        verify_that!(first_blocker_linear(&coords, 7), some(eq((6, 1))))?;
+        verify_that!(first_blocker_binary(&coords, 7), some(eq((6, 1))))?;
+        Ok(())
+    }
+}
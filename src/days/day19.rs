@@ -0,0 +1,207 @@
+use crate::variants::Variant;
+use crate::{split_sections, AdventError, FromInput, Memo, Solver};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Color {
+    W,
+    U,
+    B,
+    R,
+    G,
+}
+
+impl TryFrom<char> for Color {
+    type Error = AdventError;
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'w' => Ok(Color::W),
+            'u' => Ok(Color::U),
+            'b' => Ok(Color::B),
+            'r' => Ok(Color::R),
+            'g' => Ok(Color::G),
+            _ => Err(format!("Unknown color {}", value).into()),
+        }
+    }
+}
+
+// A choice is a slice of colors.
+type ColorString = Box<[Color]>;
+
+fn parse_color_string(s: &str) -> Result<ColorString, AdventError> {
+    s.chars().map(Color::try_from).collect()
+}
+
+fn parse_choices(s: &str) -> Result<Box<[ColorString]>, AdventError> {
+    s.split(", ").map(parse_color_string).collect()
+}
+
+#[derive(Debug, PartialEq)]
+struct Problem {
+    choices: Box<[ColorString]>,
+    designs: Box<[ColorString]>,
+}
+
+impl FromInput for Problem {
+    fn from_input(input: &str) -> Result<Self, AdventError> {
+        let [choices, designs] = split_sections(input, ["choices", "designs"])?;
+        let choices = parse_choices(choices)?;
+        let designs = designs
+            .lines()
+            .map(parse_color_string)
+            .collect::<Result<Vec<_>, _>>()?
+            .into();
+        Ok(Problem { choices, designs })
+    }
+}
+
+fn is_possible(choices: &[ColorString], pattern: &[Color]) -> bool {
+    if pattern.is_empty() {
+        return true;
+    }
+    for choice in choices {
+        if choice.len() > pattern.len() {
+            continue;
+        }
+
+        if pattern[..choice.len()] == choice[..] && is_possible(choices, &pattern[choice.len()..]) {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn count_possibles(choices: &[ColorString], pattern: &[Color]) -> u64 {
+    let mut suffix_cache = vec![0; pattern.len() + 1];
+    suffix_cache[pattern.len()] = 1;
+
+    // Work backwards
+    for i in (0..pattern.len()).rev() {
+        for choice in choices {
+            if i + choice.len() > pattern.len() {
+                continue;
+            }
+
+            if choice[..] == pattern[i..i + choice.len()] {
+                suffix_cache[i] += suffix_cache[i + choice.len()];
+            }
+        }
+    }
+    // Final count should be here:
+    suffix_cache[0]
+}
+
+fn count_possibles_memoized(choices: &[ColorString], pattern: &[Color]) -> u64 {
+    // Keyed by how much of the pattern is left to match.
+    let mut count = Memo::new(|count: &mut dyn FnMut(usize) -> u64, left: usize| {
+        let rest = &pattern[pattern.len() - left..];
+        if rest.is_empty() {
+            return 1;
+        }
+        choices
+            .iter()
+            .filter(|choice| rest.starts_with(choice))
+            .map(|choice| count(left - choice.len()))
+            .sum()
+    });
+    count.get(pattern.len())
+}
+
+/// How many designs `possible` accepts.
+fn count_designs(
+    input: &str,
+    possible: impl Fn(&[ColorString], &[Color]) -> bool,
+) -> Result<String, AdventError> {
+    let problem = Problem::from_input(input)?;
+    Ok(problem
+        .designs
+        .iter()
+        .filter(|design| possible(&problem.choices, design))
+        .count()
+        .to_string())
+}
+
+/// The total number of arrangements over all designs, by `count`.
+fn count_arrangements(
+    input: &str,
+    count: impl Fn(&[ColorString], &[Color]) -> u64,
+) -> Result<String, AdventError> {
+    let problem = Problem::from_input(input)?;
+    Ok(problem
+        .designs
+        .iter()
+        .map(|design| count(&problem.choices, design))
+        .sum::<u64>()
+        .to_string())
+}
+
+pub const PART1_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "backtracking",
+        run: |input| count_designs(input, is_possible),
+    },
+    Variant {
+        name: "suffix counts",
+        run: |input| count_designs(input, |c, p| count_possibles(c, p) > 0),
+    },
+    Variant {
+        name: "memoized counts",
+        run: |input| count_designs(input, |c, p| count_possibles_memoized(c, p) > 0),
+    },
+];
+
+pub const PART2_VARIANTS: &[Variant] = &[
+    Variant {
+        name: "suffix counts",
+        run: |input| count_arrangements(input, count_possibles),
+    },
+    Variant {
+        name: "memoized counts",
+        run: |input| count_arrangements(input, count_possibles_memoized),
+    },
+];
+
+pub struct Day19;
+
+impl Solver for Day19 {
+    fn title(&self) -> &'static str {
+        "Linen Layout"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Dynamic programming over pattern suffixes, counting the ways to build each design."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        count_designs(input, is_possible)
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        count_arrangements(input, count_possibles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_parse_choices() -> Result<()> {
+        let s = "r, wr, b, g, bwu, rb, gb, br";
+        verify_that!(
+            parse_choices(s).into_test_result()?,
+            elements_are![
+                container_eq([Color::R].into()),
+                container_eq([Color::W, Color::R].into()),
+                container_eq([Color::B].into()),
+                container_eq([Color::G].into()),
+                container_eq([Color::B, Color::W, Color::U].into()),
+                container_eq([Color::R, Color::B].into()),
+                container_eq([Color::G, Color::B].into()),
+                container_eq([Color::B, Color::R].into()),
+            ]
+        )?;
+        Ok(())
+    }
+}
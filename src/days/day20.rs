@@ -0,0 +1,168 @@
+#![allow(dead_code, unused_variables, unused_imports)]
+
+use crate::search::bfs;
+use crate::{AdventError, Direction, FromInput, Grid, Render, Solver};
+
+use std::error::Error;
+
+struct Maze {
+    grid: Grid<char>,
+    start_pos: usize,
+    end_pos: usize,
+}
+
+impl Render for Maze {
+    fn render(&self) -> String {
+        self.grid.render()
+    }
+}
+
+impl FromInput for Maze {
+    fn from_input(input: &str) -> Result<Self, AdventError> {
+        let grid = Grid::parse_chars(input, |ch| ch)?;
+        let start_pos = grid
+            .position_of(&'S')
+            .ok_or_else(|| AdventError::parse("No start"))?;
+        let end_pos = grid
+            .position_of(&'E')
+            .ok_or_else(|| AdventError::parse("No end"))?;
+        Ok(Self {
+            grid,
+            start_pos,
+            end_pos,
+        })
+    }
+}
+
+impl Maze {
+    fn costs(&self) -> Vec<u32> {
+        let distances = bfs(self.start_pos, |&pos| {
+            self.grid
+                .neighbors(pos)
+                .filter(|&next| self.grid[next] != '#')
+        });
+        (0..self.grid.len())
+            .map(|pos| distances.distance(&pos).map_or(u32::MAX, |d| d as u32))
+            .collect()
+    }
+
+    fn dig(&self, pos: usize, dir: Direction) -> Option<u32> {
+        // Drill a cheat, rerun-costs, return cost to the end position.  If we
+        // can't drill successfully, None.
+        let hole = self.grid.tiles.dir_to(pos, dir)?;
+        if self.grid[pos] != '#' && self.grid[hole] != '#' {
+            return None;
+        }
+
+        let mut new_maze = Maze {
+            grid: self.grid.clone(),
+            ..*self
+        };
+        new_maze.grid[pos] = '.';
+        new_maze.grid[hole] = '.';
+        let updated_costs = new_maze.costs();
+        Some(updated_costs[self.end_pos])
+    }
+}
+
+fn part_1(maze: &Maze) -> usize {
+    let costs = maze.costs();
+    let original_dist = costs[maze.end_pos];
+
+    (0..(maze.grid.len()))
+        .filter_map(|pos| maze.dig(pos, Direction::Right))
+        .filter(|cost| original_dist - cost >= 100)
+        .count()
+        + (0..(maze.grid.len()))
+            .filter_map(|pos| maze.dig(pos, Direction::Down))
+            .filter(|cost| original_dist - cost >= 100)
+            .count()
+}
+
+/// Counts the cheats of up to `radius` steps that save at least
+/// `min_saving`.  The track is a single path, so the cost from the start
+/// tells how far along it each tile is, and a cheat saves the distance it
+/// skips minus the steps it takes.
+fn count_cheats(maze: &Maze, radius: usize, min_saving: u32) -> usize {
+    let costs = maze.costs();
+    costs
+        .iter()
+        .enumerate()
+        .filter(|(_, &cost)| cost != u32::MAX)
+        .map(|(from, &from_cost)| {
+            maze.grid
+                .tiles
+                .within_manhattan(from, radius)
+                .filter(|&(to, steps)| {
+                    costs[to] != u32::MAX && costs[to] >= from_cost + steps as u32 + min_saving
+                })
+                .count()
+        })
+        .sum()
+}
+
+pub struct Day20;
+
+impl Solver for Day20 {
+    fn title(&self) -> &'static str {
+        "Race Condition"
+    }
+
+    fn notes(&self) -> &'static str {
+        "Re-run the BFS with each single wall removed; for part 2, pair up track tiles within 20 steps of each other."
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        Ok(part_1(&Maze::from_input(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, AdventError> {
+        Ok(count_cheats(&Maze::from_input(input)?, 20, 100).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use insta::assert_snapshot;
+
+    const DATA: &str = "\
+###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############
+";
+
+    #[gtest]
+    fn test_render() -> Result<()> {
+        assert_snapshot!(Maze::from_input(DATA)?.render());
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_count_cheats() -> Result<()> {
+        let maze = Maze::from_input(DATA)?;
+        verify_that!(count_cheats(&maze, 2, 1), eq(44))?;
+        verify_that!(count_cheats(&maze, 2, 64), eq(1))?;
+        verify_that!(count_cheats(&maze, 20, 50), eq(285))
+    }
+
+    #[gtest]
+    fn test_dig() -> Result<()> {
+        let maze = Maze::from_input(DATA)?;
+        verify_that!(maze.dig(23, Direction::Right), some(eq(72)))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+//! The error every solver returns, so the runner can report a failed part
+//! the same way whatever day it's from.
+use crate::graph::Cycle;
+use std::fmt;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AdventError {
+    /// The input isn't shaped the way the puzzle describes.
+    #[error("Parse error: {0}")]
+    Parse(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The input parsed, but has no answer: no path, no picture, a cycle
+    /// in an ordering.
+    #[error("No solution: {0}")]
+    NoSolution(String),
+    /// A part that doesn't exist, or isn't written yet.
+    #[error("Unsupported: {0}")]
+    Unsupported(String),
+}
+
+impl AdventError {
+    pub fn parse(message: impl fmt::Display) -> Self {
+        AdventError::Parse(message.to_string())
+    }
+
+    pub fn no_solution(message: impl fmt::Display) -> Self {
+        AdventError::NoSolution(message.to_string())
+    }
+}
+
+/// The shared parsing helpers, like `split_sections` and
+/// `Grid::parse_chars`, report problems as strings.
+impl From<String> for AdventError {
+    fn from(message: String) -> Self {
+        AdventError::Parse(message)
+    }
+}
+
+impl From<std::num::ParseIntError> for AdventError {
+    fn from(e: std::num::ParseIntError) -> Self {
+        AdventError::parse(e)
+    }
+}
+
+impl From<nom::Err<nom::error::Error<String>>> for AdventError {
+    fn from(e: nom::Err<nom::error::Error<String>>) -> Self {
+        AdventError::parse(e)
+    }
+}
+
+impl<T: fmt::Debug> From<Cycle<T>> for AdventError {
+    fn from(cycle: Cycle<T>) -> Self {
+        AdventError::no_solution(cycle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_messages() -> Result<()> {
+        verify_that!(
+            AdventError::from("x".parse::<u32>().unwrap_err()).to_string(),
+            eq("Parse error: invalid digit found in string")
+        )?;
+        verify_that!(
+            AdventError::from(Cycle(vec![1, 2])).to_string(),
+            eq("No solution: Ordering has a cycle: 1 -> 2 -> 1")
+        )?;
+        verify_that!(
+            AdventError::no_solution("No path to goal").to_string(),
+            eq("No solution: No path to goal")
+        )
+    }
+}
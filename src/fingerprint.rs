@@ -0,0 +1,116 @@
+//! Compact digests of search states, so a seen-set can keep a `u64` per
+//! state instead of a clone of the whole grid or agent it came from.
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// A 64-bit digest of a state.  Unlike the state itself, two different
+/// states can share a fingerprint, so a set of them can claim a state was
+/// seen when it wasn't.  With `n` states the chance of that is about
+/// `n² / 2⁶⁵`: negligible for the millions of states a puzzle search
+/// visits, but keep the real states when an answer has to be exact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Fingerprint(pub u64);
+
+impl Fingerprint {
+    /// Fingerprints any hashable value.  The hasher has fixed keys, so the
+    /// same value gets the same fingerprint on every run.
+    pub fn of<T: Hash + ?Sized>(value: &T) -> Self {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+}
+
+/// Zobrist hashing: a random key for every value each cell can hold,
+/// XORed together over a state's cells.  Changing a cell changes the
+/// fingerprint by two XORs, so a search that moves one box or one agent at
+/// a time never has to rehash the whole grid.
+pub struct Zobrist {
+    values: usize,
+    keys: Box<[u64]>,
+}
+
+impl Zobrist {
+    /// Keys for `cells` cells that each hold one of `values` values.  The
+    /// keys come from a fixed seed, so fingerprints agree from run to run.
+    pub fn new(cells: usize, values: usize) -> Self {
+        let mut seed = 0x2024_u64;
+        let keys = (0..cells * values).map(|_| splitmix64(&mut seed)).collect();
+        Self { values, keys }
+    }
+
+    fn key(&self, cell: usize, value: usize) -> u64 {
+        assert!(value < self.values, "Value {} out of range", value);
+        self.keys[cell * self.values + value]
+    }
+
+    /// The fingerprint of a state, given as the cells that hold something
+    /// and what they hold.  The order of the cells doesn't matter.
+    pub fn hash(&self, cells: impl IntoIterator<Item = (usize, usize)>) -> Fingerprint {
+        Fingerprint(
+            cells
+                .into_iter()
+                .fold(0, |acc, (cell, value)| acc ^ self.key(cell, value)),
+        )
+    }
+
+    /// `fingerprint` with `value` put in `cell` if it wasn't there, or
+    /// taken out if it was.  Moving a value is toggling it out of one cell
+    /// and into another.
+    pub fn toggle(&self, fingerprint: Fingerprint, cell: usize, value: usize) -> Fingerprint {
+        Fingerprint(fingerprint.0 ^ self.key(cell, value))
+    }
+}
+
+/// The SplitMix64 generator: good enough for hashing keys, and small
+/// enough not to need a dependency.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FxHashSet;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_of() -> Result<()> {
+        verify_that!(Fingerprint::of("abc"), eq(Fingerprint::of("abc")))?;
+        verify_that!(Fingerprint::of("abc"), not(eq(Fingerprint::of("abd"))))?;
+        verify_that!(Fingerprint::of(&(1, 2)), not(eq(Fingerprint::of(&(2, 1)))))
+    }
+
+    #[gtest]
+    fn test_keys_are_distinct() -> Result<()> {
+        let zobrist = Zobrist::new(100, 4);
+        let keys: FxHashSet<_> = zobrist.keys.iter().collect();
+        verify_that!(keys.len(), eq(400))
+    }
+
+    #[gtest]
+    fn test_hash_ignores_order() -> Result<()> {
+        let zobrist = Zobrist::new(10, 2);
+        verify_that!(
+            zobrist.hash([(1, 0), (4, 1), (7, 0)]),
+            eq(zobrist.hash([(7, 0), (1, 0), (4, 1)]))
+        )?;
+        verify_that!(
+            zobrist.hash([(1, 0), (4, 1)]),
+            not(eq(zobrist.hash([(1, 1), (4, 0)])))
+        )?;
+        verify_that!(zobrist.hash([]), eq(Fingerprint(0)))
+    }
+
+    #[gtest]
+    fn test_toggle_matches_rehashing() -> Result<()> {
+        let zobrist = Zobrist::new(10, 2);
+        let before = zobrist.hash([(1, 0), (4, 1)]);
+        let moved = zobrist.toggle(zobrist.toggle(before, 4, 1), 5, 1);
+        verify_that!(moved, eq(zobrist.hash([(1, 0), (5, 1)])))?;
+        verify_that!(zobrist.toggle(zobrist.toggle(moved, 3, 0), 3, 0), eq(moved))
+    }
+}
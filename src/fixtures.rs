@@ -0,0 +1,370 @@
+//! The worked examples from each day's puzzle text, with their published
+//! answers.
+//!
+//! Days 14, 18 and 20 are missing: their examples use a smaller grid or a
+//! different threshold than the real puzzle, which the solvers hardcode.
+use crate::Solver;
+
+pub struct Sample {
+    pub input: &'static str,
+    pub part1: &'static str,
+    /// `None` where the puzzle doesn't give a part 2 answer for the example.
+    pub part2: Option<&'static str>,
+}
+
+pub const DAY01: Sample = Sample {
+    input: "\
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+",
+    part1: "11",
+    part2: Some("31"),
+};
+
+pub const DAY02: Sample = Sample {
+    input: "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9",
+    part1: "2",
+    part2: Some("4"),
+};
+
+/// The part 2 example; without `do()`/`don't()` it still sums to part 1's
+/// answer.
+pub const DAY03: Sample = Sample {
+    input: "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))",
+    part1: "161",
+    part2: Some("48"),
+};
+
+pub const DAY04: Sample = Sample {
+    input: "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX",
+    part1: "18",
+    part2: Some("9"),
+};
+
+pub const DAY05: Sample = Sample {
+    input: "\
+47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+",
+    part1: "143",
+    part2: Some("123"),
+};
+
+pub const DAY06: Sample = Sample {
+    input: "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...",
+    part1: "41",
+    part2: Some("6"),
+};
+
+pub const DAY07: Sample = Sample {
+    input: "\
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20",
+    part1: "3749",
+    part2: Some("11387"),
+};
+
+pub const DAY08: Sample = Sample {
+    input: "\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............",
+    part1: "14",
+    part2: Some("34"),
+};
+
+pub const DAY09: Sample = Sample {
+    input: "2333133121414131402",
+    part1: "1928",
+    part2: Some("2858"),
+};
+
+pub const DAY10: Sample = Sample {
+    input: "\
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732",
+    part1: "36",
+    part2: Some("81"),
+};
+
+pub const DAY11: Sample = Sample {
+    input: "125 17",
+    part1: "55312",
+    part2: Some("65601038650482"),
+};
+
+pub const DAY12: Sample = Sample {
+    input: "\
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+",
+    part1: "1930",
+    part2: Some("1206"),
+};
+
+pub const DAY13: Sample = Sample {
+    input: "\
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+",
+    part1: "480",
+    part2: None,
+};
+
+pub const DAY15: Sample = Sample {
+    input: "\
+##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
+",
+    part1: "10092",
+    part2: Some("9021"),
+};
+
+pub const DAY16: Sample = Sample {
+    input: "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############
+",
+    part1: "7036",
+    part2: Some("45"),
+};
+
+pub const DAY17: Sample = Sample {
+    input: "\
+Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0
+",
+    part1: "4,6,3,5,6,3,5,2,1,0",
+    part2: None,
+};
+
+pub const DAY19: Sample = Sample {
+    input: "\
+r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb
+",
+    part1: "6",
+    part2: Some("16"),
+};
+
+/// Every day with a usable example, in order.
+pub const SAMPLES: &[(u8, &Sample)] = &[
+    (1, &DAY01),
+    (2, &DAY02),
+    (3, &DAY03),
+    (4, &DAY04),
+    (5, &DAY05),
+    (6, &DAY06),
+    (7, &DAY07),
+    (8, &DAY08),
+    (9, &DAY09),
+    (10, &DAY10),
+    (11, &DAY11),
+    (12, &DAY12),
+    (13, &DAY13),
+    (15, &DAY15),
+    (16, &DAY16),
+    (17, &DAY17),
+    (19, &DAY19),
+];
+
+pub fn sample(day: u8) -> Option<&'static Sample> {
+    SAMPLES
+        .iter()
+        .find(|(d, _)| *d == day)
+        .map(|(_, sample)| *sample)
+}
+
+/// Runs `solver` on `day`'s example and prints each answer beside the
+/// expected one.
+pub fn print_sample_answers(
+    solver: &dyn Solver,
+    day: u8,
+    part: Option<u8>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let sample = sample(day).ok_or_else(|| format!("Day {} has no bundled sample", day))?;
+    if part != Some(2) {
+        let actual = solver.part1(sample.input)?;
+        println!("Part 1: {}", compare(&actual, Some(sample.part1)));
+    }
+    if part != Some(1) {
+        let actual = solver.part2(sample.input)?;
+        println!("Part 2: {}", compare(&actual, sample.part2));
+    }
+    Ok(())
+}
+
+fn compare(actual: &str, expected: Option<&str>) -> String {
+    match expected {
+        Some(expected) if expected == actual => format!("{} (ok)", actual),
+        Some(expected) => format!("{} (expected {})", actual, expected),
+        None => format!("{} (no expected answer)", actual),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_samples() -> Result<()> {
+        for (day, sample) in SAMPLES {
+            let solver = crate::solver(*day).unwrap();
+            verify_that!(solver.part1(sample.input).ok(), some(eq(sample.part1)))?;
+            if let Some(part2) = sample.part2 {
+                verify_that!(solver.part2(sample.input).ok(), some(eq(part2)))?;
+            }
+        }
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_compare() -> Result<()> {
+        verify_that!(compare("11", Some("11")), eq("11 (ok)"))?;
+        verify_that!(compare("12", Some("11")), eq("12 (expected 11)"))?;
+        verify_that!(compare("12", None), eq("12 (no expected answer)"))
+    }
+}
@@ -0,0 +1,132 @@
+//! Areas of lattice polygons, given their vertices in order around the
+//! boundary, in either direction.
+//!
+//! A polygon traced on the grid, as in a "dig a trench, then fill it in"
+//! puzzle, covers its interior points and its boundary points; Pick's
+//! theorem gets both from the shoelace area without flood-filling.
+use crate::{Direction, Vec2};
+
+/// Twice the area enclosed by `vertices`, by the shoelace formula.  Lattice
+/// polygons can have half-integer areas, so this stays exact.
+pub fn double_area(vertices: &[Vec2]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| a.0 * b.1 - b.0 * a.1)
+        .sum::<i64>()
+        .abs()
+}
+
+/// The number of lattice points on the boundary of the polygon.
+pub fn boundary_points(vertices: &[Vec2]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| {
+            let d = b - a;
+            gcd(d.0.abs(), d.1.abs())
+        })
+        .sum()
+}
+
+/// The number of lattice points strictly inside the polygon, by Pick's
+/// theorem: `A = i + b/2 - 1`.
+pub fn interior_points(vertices: &[Vec2]) -> i64 {
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+/// The number of lattice points inside or on the polygon: the tiles a
+/// trench and its filling cover.
+pub fn lattice_points(vertices: &[Vec2]) -> i64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+/// The vertices visited by walking from `start`, moving `n` steps in each
+/// direction in turn.  The start is the first vertex; if the walk comes
+/// back to it, it isn't repeated at the end.
+pub fn trace(start: Vec2, moves: impl IntoIterator<Item = (Direction, i64)>) -> Vec<Vec2> {
+    let mut vertices = vec![start];
+    let mut pos = start;
+    for (dir, n) in moves {
+        pos += n * Vec2::from(dir);
+        vertices.push(pos);
+    }
+    if vertices.len() > 1 && vertices.last() == Some(&start) {
+        vertices.pop();
+    }
+    vertices
+}
+
+/// Each edge of the closed polygon, including the one back to the start.
+fn edges(vertices: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+fn gcd(mut a: i64, mut b: i64) -> i64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_square() -> Result<()> {
+        let square = [Vec2(0, 0), Vec2(4, 0), Vec2(4, 4), Vec2(0, 4)];
+        verify_that!(double_area(&square), eq(32))?;
+        verify_that!(boundary_points(&square), eq(16))?;
+        verify_that!(interior_points(&square), eq(9))?;
+        verify_that!(lattice_points(&square), eq(25))
+    }
+
+    #[gtest]
+    fn test_orientation_does_not_matter() -> Result<()> {
+        let clockwise = [Vec2(0, 0), Vec2(3, 0), Vec2(0, 2)];
+        let counterclockwise = [Vec2(0, 0), Vec2(0, 2), Vec2(3, 0)];
+        verify_that!(double_area(&clockwise), eq(6))?;
+        verify_that!(double_area(&counterclockwise), eq(6))
+    }
+
+    #[gtest]
+    fn test_half_integer_area() -> Result<()> {
+        // A = 3 / 2, with one point strictly inside.
+        let triangle = [Vec2(0, 0), Vec2(2, 1), Vec2(1, 2)];
+        verify_that!(double_area(&triangle), eq(3))?;
+        verify_that!(boundary_points(&triangle), eq(3))?;
+        verify_that!(interior_points(&triangle), eq(1))
+    }
+
+    #[gtest]
+    fn test_trace_lagoon() -> Result<()> {
+        // The example trench from 2023's day 18.
+        let moves = [
+            (Direction::Right, 6),
+            (Direction::Down, 5),
+            (Direction::Left, 2),
+            (Direction::Down, 2),
+            (Direction::Right, 2),
+            (Direction::Down, 2),
+            (Direction::Left, 5),
+            (Direction::Up, 2),
+            (Direction::Left, 1),
+            (Direction::Up, 2),
+            (Direction::Right, 2),
+            (Direction::Up, 3),
+            (Direction::Left, 2),
+            (Direction::Up, 2),
+        ];
+        let vertices = trace(Vec2(0, 0), moves);
+        verify_that!(vertices.len(), eq(14))?;
+        verify_that!(boundary_points(&vertices), eq(38))?;
+        verify_that!(lattice_points(&vertices), eq(62))
+    }
+
+    #[gtest]
+    fn test_degenerate() -> Result<()> {
+        verify_that!(double_area(&[]), eq(0))?;
+        verify_that!(double_area(&[Vec2(1, 1)]), eq(0))
+    }
+}
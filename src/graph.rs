@@ -0,0 +1,436 @@
+//! Graphs given as edges between plain values, for puzzles that aren't
+//! laid out on a grid.
+use crate::{FxHashMap, FxHashSet};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::fmt;
+use std::hash::Hash;
+
+/// Yields nodes so that each comes after every node with an edge to it,
+/// given edges as `(parent, child)`.  Nodes in a cycle are never yielded;
+/// `sorted` reports them.
+pub struct TopologicalSort<T> {
+    available: Vec<T>,
+    pending: FxHashMap<T, Vec<T>>,
+    counts: FxHashMap<T, usize>,
+    /// When set, `available` is kept sorted so the least node comes next.
+    tie_break: Option<fn(&T, &T) -> Ordering>,
+}
+
+impl<T> TopologicalSort<T>
+where
+    T: Eq + Hash + Copy,
+{
+    pub fn new(deps: impl IntoIterator<Item = (T, T)>) -> Self {
+        let mut pending: FxHashMap<T, Vec<T>> = FxHashMap::default();
+        let mut counts: FxHashMap<T, usize> = FxHashMap::default();
+        for (parent, child) in deps {
+            pending.entry(parent).or_default().push(child);
+            counts.entry(parent).or_insert(0);
+            *counts.entry(child).or_insert(0) += 1;
+        }
+        let available = counts
+            .iter()
+            .filter(|&(_, &count)| count == 0)
+            .map(|(&node, _)| node)
+            .collect();
+
+        Self {
+            available,
+            pending,
+            counts,
+            tie_break: None,
+        }
+    }
+
+    /// Also yields `nodes` that no edge mentions, so every one of them comes
+    /// out of the sort rather than only those with an ordering.
+    pub fn with_nodes(mut self, nodes: impl IntoIterator<Item = T>) -> Self {
+        for node in nodes {
+            if let Entry::Vacant(entry) = self.counts.entry(node) {
+                entry.insert(0);
+                self.available.push(node);
+            }
+        }
+        self.sort_available();
+        self
+    }
+
+    /// Breaks ties by yielding the least available node first, so the
+    /// order doesn't depend on hashing.
+    pub fn stable(mut self) -> Self
+    where
+        T: Ord,
+    {
+        self.tie_break = Some(T::cmp);
+        self.sort_available();
+        self
+    }
+
+    fn sort_available(&mut self) {
+        if let Some(cmp) = self.tie_break {
+            self.available.sort_by(|a, b| cmp(b, a));
+        }
+    }
+
+    /// Every node in order, or the nodes of a cycle, in order around it, if
+    /// there is one.
+    pub fn sorted(mut self) -> Result<Vec<T>, Cycle<T>> {
+        let sorted: Vec<T> = self.by_ref().collect();
+        if sorted.len() == self.counts.len() {
+            return Ok(sorted);
+        }
+
+        // Every node left has a parent that's also left, so walking back
+        // through parents must come round to a node already seen.
+        let parent_of = |child: &T| {
+            self.pending
+                .iter()
+                .find(|(_, children)| children.contains(child))
+                .map(|(&parent, _)| parent)
+        };
+        let mut path: Vec<T> = self
+            .counts
+            .iter()
+            .find(|&(_, &count)| count > 0)
+            .map(|(&node, _)| node)
+            .into_iter()
+            .collect();
+        while let Some(parent) = path.last().and_then(parent_of) {
+            if let Some(start) = path.iter().position(|&node| node == parent) {
+                let mut cycle = path.split_off(start);
+                cycle.reverse();
+                return Err(Cycle(cycle));
+            }
+            path.push(parent);
+        }
+        unreachable!("Unsorted nodes without a cycle")
+    }
+}
+
+impl<T> Iterator for TopologicalSort<T>
+where
+    T: Eq + Hash + Copy,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let result = self.available.pop();
+
+        if let Some(parent) = result {
+            let children = self.pending.remove(&parent).into_iter().flatten();
+            for child in children {
+                let child_count = self.counts.entry(child).or_insert(1);
+                *child_count = child_count.saturating_sub(1);
+                if *child_count == 0 {
+                    self.available.push(child);
+                }
+            }
+            self.sort_available();
+        }
+
+        result
+    }
+}
+
+/// Nodes that each must come before the next, and the last before the
+/// first.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Cycle<T>(pub Vec<T>);
+
+impl<T: fmt::Debug> fmt::Display for Cycle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Ordering has a cycle:")?;
+        for node in &self.0 {
+            write!(f, " {:?} ->", node)?;
+        }
+        match self.0.first() {
+            Some(first) => write!(f, " {:?}", first),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for Cycle<T> {}
+
+/// An undirected graph, as each node's set of neighbors.
+pub type Adjacency<T> = FxHashMap<T, FxHashSet<T>>;
+
+/// The undirected graph with `edges`, each added both ways.
+pub fn adjacency<T: Eq + Hash + Copy>(edges: impl IntoIterator<Item = (T, T)>) -> Adjacency<T> {
+    let mut graph: Adjacency<T> = FxHashMap::default();
+    for (a, b) in edges {
+        graph.entry(a).or_default().insert(b);
+        graph.entry(b).or_default().insert(a);
+    }
+    graph
+}
+
+/// Every set of three mutually connected nodes, each in ascending order,
+/// and each found once.
+pub fn triangles<T: Eq + Hash + Copy + Ord>(graph: &Adjacency<T>) -> Vec<[T; 3]> {
+    let mut result = Vec::new();
+    for (&a, neighbors) in graph {
+        for &b in neighbors.iter().filter(|&&b| a < b) {
+            for &c in graph[&b].iter().filter(|&&c| b < c) {
+                if neighbors.contains(&c) {
+                    result.push([a, b, c]);
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Every maximal clique: sets of mutually connected nodes that no other
+/// node could join.  Bron–Kerbosch, pivoting on the node with the most
+/// candidates as neighbors to skip cliques that can't be maximal.
+pub fn maximal_cliques<T: Eq + Hash + Copy>(graph: &Adjacency<T>) -> Vec<Vec<T>> {
+    fn extend<T: Eq + Hash + Copy>(
+        graph: &Adjacency<T>,
+        clique: &mut Vec<T>,
+        mut candidates: FxHashSet<T>,
+        mut excluded: FxHashSet<T>,
+        result: &mut Vec<Vec<T>>,
+    ) {
+        if candidates.is_empty() && excluded.is_empty() {
+            result.push(clique.clone());
+            return;
+        }
+        let pivot = candidates
+            .union(&excluded)
+            .max_by_key(|node| graph[node].intersection(&candidates).count())
+            .copied();
+        let to_try: Vec<T> = match pivot {
+            Some(pivot) => candidates.difference(&graph[&pivot]).copied().collect(),
+            None => candidates.iter().copied().collect(),
+        };
+        for node in to_try {
+            let neighbors = &graph[&node];
+            clique.push(node);
+            extend(
+                graph,
+                clique,
+                candidates.intersection(neighbors).copied().collect(),
+                excluded.intersection(neighbors).copied().collect(),
+                result,
+            );
+            clique.pop();
+            candidates.remove(&node);
+            excluded.insert(node);
+        }
+    }
+
+    let mut result = Vec::new();
+    extend(
+        graph,
+        &mut Vec::new(),
+        graph.keys().copied().collect(),
+        FxHashSet::default(),
+        &mut result,
+    );
+    result
+}
+
+/// The largest clique, in ascending order.  Ties go to the least, to
+/// keep answers stable.
+pub fn maximum_clique<T: Eq + Hash + Copy + Ord>(graph: &Adjacency<T>) -> Vec<T> {
+    maximal_cliques(graph)
+        .into_iter()
+        .map(|mut clique| {
+            clique.sort();
+            clique
+        })
+        .max_by(|a, b| a.len().cmp(&b.len()).then_with(|| b.cmp(a)))
+        .unwrap_or_default()
+}
+
+/// Which of the numbers `0..n` have been joined together, where joining
+/// is transitive: union-find, with path halving and union by size, so
+/// each operation is close to constant time.
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl DisjointSet {
+    /// `n` numbers, each in a set of its own.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parents: (0..n).collect(),
+            sizes: vec![1; n],
+        }
+    }
+
+    /// The number standing for the set `x` is in.  Two numbers are in the
+    /// same set just when they have the same root.
+    pub fn find(&mut self, mut x: usize) -> usize {
+        while self.parents[x] != x {
+            self.parents[x] = self.parents[self.parents[x]];
+            x = self.parents[x];
+        }
+        x
+    }
+
+    /// Joins the sets `a` and `b` are in, returning false if they already
+    /// were the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (big, small) = if self.sizes[a] < self.sizes[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        self.parents[small] = big;
+        self.sizes[big] += self.sizes[small];
+        true
+    }
+
+    /// The number of numbers in the set `x` is in.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.sizes[root]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_topological() -> Result<()> {
+        let mut topsort = TopologicalSort::new([(2, 3), (1, 2)]);
+        verify_that!(topsort.next(), some(eq(1)))?;
+        verify_that!(topsort.next(), some(eq(2)))?;
+        verify_that!(topsort.next(), some(eq(3)))?;
+        verify_that!(topsort.next(), none())?;
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_topological2() -> Result<()> {
+        // This tests the last example given in 2024 day 5's part 2
+        // description, since it's the most sophisticated.
+        let topsort = TopologicalSort::new([
+            (29, 13),
+            (47, 13),
+            (75, 13),
+            (97, 13),
+            (75, 47),
+            (97, 47),
+            (97, 75),
+            (47, 29),
+            (75, 29),
+            (97, 29),
+        ]);
+        verify_that!(topsort.sorted(), ok(eq(&vec![97, 75, 47, 29, 13])))
+    }
+
+    #[gtest]
+    fn test_stable() -> Result<()> {
+        // 1 and 5 are both free to go first, as are 2 and 3 after 1.
+        let deps = [(1, 2), (1, 3), (5, 4), (3, 4), (2, 4)];
+        verify_that!(
+            TopologicalSort::new(deps).stable().sorted(),
+            ok(eq(&vec![1, 2, 3, 5, 4]))
+        )
+    }
+
+    #[gtest]
+    fn test_cycle() -> Result<()> {
+        // 1 leads into the cycle and 5 hangs off it; neither is part of it.
+        let deps = [(1, 2), (2, 3), (3, 4), (4, 2), (4, 5)];
+        let mut cycle = match TopologicalSort::new(deps).sorted() {
+            Err(Cycle(cycle)) => cycle,
+            Ok(sorted) => return fail!("Expected a cycle, got {:?}", sorted),
+        };
+        verify_that!(cycle, unordered_elements_are![&2, &3, &4])?;
+        let at = cycle.iter().position(|&n| n == 2).unwrap_or_default();
+        cycle.rotate_left(at);
+        verify_that!(cycle, eq(&vec![2, 3, 4]))
+    }
+
+    #[gtest]
+    fn test_with_nodes() -> Result<()> {
+        verify_that!(
+            TopologicalSort::new([(2, 1)])
+                .with_nodes([1, 2, 3])
+                .stable()
+                .sorted(),
+            ok(eq(&vec![2, 1, 3]))
+        )
+    }
+
+    #[gtest]
+    fn test_cycle_message() -> Result<()> {
+        verify_that!(
+            Cycle(vec!['a', 'b']).to_string(),
+            eq("Ordering has a cycle: 'a' -> 'b' -> 'a'")
+        )
+    }
+
+    /// The example network from 2024 day 23.
+    const LAN: &str = "\
+kh-tc qp-kh de-cg ka-co yn-aq qp-ub cg-tb vc-aq tb-ka wh-tc yn-cg kh-ub ta-co de-co tc-td tb-wq
+wh-td ta-ka td-qp aq-cg wq-ub ub-vc de-ta wq-aq wq-vc wh-yn ka-de kh-ta co-tc wh-qp tb-vc td-yn";
+
+    fn lan() -> Adjacency<&'static str> {
+        adjacency(
+            LAN.split_whitespace()
+                .filter_map(|edge| edge.split_once('-')),
+        )
+    }
+
+    #[gtest]
+    fn test_triangles() -> Result<()> {
+        let triangles = triangles(&lan());
+        verify_that!(triangles.len(), eq(12))?;
+        verify_that!(
+            triangles
+                .iter()
+                .filter(|t| t.iter().any(|node| node.starts_with('t')))
+                .count(),
+            eq(7)
+        )?;
+        verify_that!(triangles, contains(eq(&["co", "de", "ta"])))
+    }
+
+    #[gtest]
+    fn test_maximum_clique() -> Result<()> {
+        verify_that!(maximum_clique(&lan()), eq(&vec!["co", "de", "ka", "ta"]))?;
+        verify_that!(maximum_clique(&adjacency::<u8>([])), eq(&vec![]))
+    }
+
+    #[gtest]
+    fn test_maximal_cliques() -> Result<()> {
+        // A triangle with a tail, and a separate edge.
+        let graph = adjacency([(1, 2), (2, 3), (1, 3), (3, 4), (5, 6)]);
+        let mut cliques: Vec<Vec<i32>> = maximal_cliques(&graph)
+            .into_iter()
+            .map(|mut clique| {
+                clique.sort();
+                clique
+            })
+            .collect();
+        cliques.sort();
+        verify_that!(cliques, eq(&vec![vec![1, 2, 3], vec![3, 4], vec![5, 6]]))
+    }
+
+    #[gtest]
+    fn test_disjoint_set() -> Result<()> {
+        let mut sets = DisjointSet::new(6);
+        verify_that!(sets.union(0, 1), is_true())?;
+        verify_that!(sets.union(2, 3), is_true())?;
+        verify_that!(sets.union(1, 3), is_true())?;
+        verify_that!(sets.union(0, 2), is_false())?;
+        verify_that!(sets.find(0), eq(sets.find(3)))?;
+        verify_that!(sets.find(4), not(eq(sets.find(0))))?;
+        verify_that!(sets.size(2), eq(4))?;
+        verify_that!(sets.size(5), eq(1))
+    }
+}
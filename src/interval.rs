@@ -0,0 +1,232 @@
+//! Half-open ranges of integers, and sets of them kept as disjoint runs,
+//! for puzzles about extents: free space on a disk, seeds mapped range to
+//! range, or the rows and columns in a quadrant.
+use std::ops::Range;
+
+/// The integers from `start` up to, but not including, `end`.  Empty when
+/// `end <= start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    /// The number of integers covered.
+    pub fn len(&self) -> i64 {
+        (self.end - self.start).max(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, x: i64) -> bool {
+        self.start <= x && x < self.end
+    }
+
+    /// The integers in both, if there are any.
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        let overlap = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    /// The integers in either, if that's a single interval: they overlap or
+    /// one ends where the other starts.
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+        (self.start <= other.end && other.start <= self.end)
+            .then(|| Interval::new(self.start.min(other.start), self.end.max(other.end)))
+    }
+
+    /// The part before `x` and the part from `x` on; either may be empty.
+    pub fn split_at(&self, x: i64) -> (Interval, Interval) {
+        let x = x.clamp(self.start, self.end.max(self.start));
+        (Interval::new(self.start, x), Interval::new(x, self.end))
+    }
+
+    /// The nonempty pieces left after taking out `other`: at most one
+    /// before it and one after.
+    pub fn difference(&self, other: &Interval) -> impl Iterator<Item = Interval> {
+        let (before, _) = self.split_at(other.start);
+        let (_, after) = self.split_at(other.end.max(other.start));
+        [before, after]
+            .into_iter()
+            .filter(|piece| !piece.is_empty())
+    }
+}
+
+impl From<Range<i64>> for Interval {
+    fn from(range: Range<i64>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+/// A set of integers, kept as sorted intervals that neither overlap nor
+/// touch.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every integer in `interval`, merging it with any runs it
+    /// overlaps or touches.
+    pub fn insert(&mut self, interval: Interval) {
+        if interval.is_empty() {
+            return;
+        }
+        let first = self.intervals.partition_point(|iv| iv.end < interval.start);
+        let last = self
+            .intervals
+            .partition_point(|iv| iv.start <= interval.end);
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |acc, iv| acc.union(iv).unwrap_or(acc));
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Takes out every integer in `interval`, splitting runs as needed.
+    pub fn remove(&mut self, interval: Interval) {
+        if interval.is_empty() {
+            return;
+        }
+        let first = self
+            .intervals
+            .partition_point(|iv| iv.end <= interval.start);
+        let last = self.intervals.partition_point(|iv| iv.start < interval.end);
+        let pieces: Vec<Interval> = self.intervals[first..last]
+            .iter()
+            .flat_map(|iv| iv.difference(&interval))
+            .collect();
+        self.intervals.splice(first..last, pieces);
+    }
+
+    pub fn contains(&self, x: i64) -> bool {
+        let at = self.intervals.partition_point(|iv| iv.end <= x);
+        self.intervals.get(at).is_some_and(|iv| iv.contains(x))
+    }
+
+    /// The total number of integers in the set.
+    pub fn covered(&self) -> i64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The runs, from lowest to highest.
+    pub fn iter(&self) -> impl Iterator<Item = &Interval> {
+        self.intervals.iter()
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(intervals: I) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in intervals {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_interval() -> Result<()> {
+        let a = Interval::from(2..7);
+        verify_that!(a.len(), eq(5))?;
+        verify_that!(Interval::new(4, 1).len(), eq(0))?;
+        verify_that!((a.contains(2), a.contains(7)), eq((true, false)))?;
+        verify_that!(
+            a.intersection(&(5..9).into()),
+            some(eq(Interval::new(5, 7)))
+        )?;
+        verify_that!(a.intersection(&(7..9).into()), none())?;
+        verify_that!(a.union(&(7..9).into()), some(eq(Interval::new(2, 9))))?;
+        verify_that!(a.union(&(8..9).into()), none())?;
+        verify_that!(
+            a.split_at(4),
+            eq((Interval::new(2, 4), Interval::new(4, 7)))
+        )?;
+        verify_that!(
+            a.split_at(10),
+            eq((Interval::new(2, 7), Interval::new(7, 7)))
+        )
+    }
+
+    #[gtest]
+    fn test_difference() -> Result<()> {
+        let a = Interval::from(0..10);
+        verify_that!(
+            a.difference(&(4..5).into()).collect::<Vec<_>>(),
+            eq(&vec![Interval::new(0, 4), Interval::new(5, 10)])
+        )?;
+        verify_that!(
+            a.difference(&(-3..2).into()).collect::<Vec<_>>(),
+            eq(&vec![Interval::new(2, 10)])
+        )?;
+        verify_that!(
+            a.difference(&(20..30).into()).collect::<Vec<_>>(),
+            eq(&vec![a])
+        )?;
+        verify_that!(a.difference(&(-1..11).into()).count(), eq(0))
+    }
+
+    #[gtest]
+    fn test_set_insert_merges() -> Result<()> {
+        let mut set: IntervalSet = [Interval::new(0, 2), Interval::new(5, 7)]
+            .into_iter()
+            .collect();
+        set.insert(Interval::new(10, 12));
+        verify_that!(set.iter().count(), eq(3))?;
+        // Touching counts as merging.
+        set.insert(Interval::new(2, 3));
+        // Bridges two runs.
+        set.insert(Interval::new(6, 10));
+        verify_that!(
+            set.iter().copied().collect::<Vec<_>>(),
+            eq(&vec![Interval::new(0, 3), Interval::new(5, 12)])
+        )?;
+        verify_that!(set.covered(), eq(10))?;
+        verify_that!((set.contains(4), set.contains(5)), eq((false, true)))
+    }
+
+    #[gtest]
+    fn test_set_remove_splits() -> Result<()> {
+        let mut set: IntervalSet = [Interval::new(0, 10), Interval::new(20, 30)]
+            .into_iter()
+            .collect();
+        set.remove(Interval::new(3, 5));
+        set.remove(Interval::new(8, 25));
+        verify_that!(
+            set.iter().copied().collect::<Vec<_>>(),
+            eq(&vec![
+                Interval::new(0, 3),
+                Interval::new(5, 8),
+                Interval::new(25, 30)
+            ])
+        )?;
+        verify_that!(set.covered(), eq(11))?;
+        set.remove(Interval::new(-100, 100));
+        verify_that!(set.is_empty(), is_true())
+    }
+}
@@ -1,3 +1,466 @@
+pub mod aoc;
+pub mod cli;
+pub mod days;
+pub mod error;
+pub mod fingerprint;
+pub mod fixtures;
+pub mod geometry;
+pub mod graph;
+pub mod interval;
+pub mod logging;
+pub mod matrix;
+pub mod number;
+pub mod prelude;
+pub mod profile;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod variants;
+pub mod verify;
+pub mod wordsearch;
+
+pub use error::AdventError;
+pub use fingerprint::{Fingerprint, Zobrist};
+use std::collections::VecDeque;
+use std::error::Error;
+pub use wordsearch::{Stencil, WordSearch};
+
+/// A day's puzzle solution.  Each part takes the raw puzzle input and
+/// returns its answer ready for printing.
+pub trait Solver {
+    /// The puzzle's title, as on adventofcode.com.
+    fn title(&self) -> &'static str;
+
+    /// A sentence or two on how the solution works, for reports.
+    fn notes(&self) -> &'static str;
+
+    fn part1(&self, input: &str) -> Result<String, AdventError>;
+    fn part2(&self, input: &str) -> Result<String, AdventError>;
+
+    /// Runs part 1 or part 2.
+    fn part(&self, part: u8, input: &str) -> Result<String, AdventError> {
+        match part {
+            1 => self.part1(input),
+            2 => self.part2(input),
+            _ => Err(AdventError::Unsupported(format!(
+                "There is no part {}",
+                part
+            ))),
+        }
+    }
+}
+
+/// A day's problem, parsed from the raw puzzle input.  Keeping parsing
+/// here leaves the solving code free to assume well-formed input.
+pub trait FromInput: Sized {
+    fn from_input(input: &str) -> Result<Self, AdventError>;
+}
+
+/// Every implemented day, in order.
+pub const SOLVERS: &[(u8, &dyn Solver)] = &[
+    (1, &days::day01::Day01),
+    (2, &days::day02::Day02),
+    (3, &days::day03::Day03),
+    (4, &days::day04::Day04),
+    (5, &days::day05::Day05),
+    (6, &days::day06::Day06),
+    (7, &days::day07::Day07),
+    (8, &days::day08::Day08),
+    (9, &days::day09::Day09),
+    (10, &days::day10::Day10),
+    (11, &days::day11::Day11),
+    (12, &days::day12::Day12),
+    (13, &days::day13::Day13),
+    (14, &days::day14::Day14),
+    (15, &days::day15::Day15),
+    (16, &days::day16::Day16),
+    (17, &days::day17::Day17),
+    (18, &days::day18::Day18),
+    (19, &days::day19::Day19),
+    (20, &days::day20::Day20),
+];
+
+/// Looks up the solver for `day`, if that day has been implemented.
+pub fn solver(day: u8) -> Option<&'static dyn Solver> {
+    SOLVERS
+        .iter()
+        .find(|(d, _)| *d == day)
+        .map(|(_, solver)| *solver)
+}
+
+/// Prints the answer to `part` of the puzzle, or to both parts when `part`
+/// is `None`.
+pub fn print_answers(
+    solver: &dyn Solver,
+    input: &str,
+    part: Option<u8>,
+) -> Result<(), Box<dyn Error>> {
+    if part != Some(2) {
+        println!("Part 1: {}", solver.part1(input)?);
+    }
+    if part != Some(1) {
+        println!("Part 2: {}", solver.part2(input)?);
+    }
+    Ok(())
+}
+
+/// Fast hash collections.  Unlike the std defaults these aren't randomly
+/// seeded, so iteration order is the same from run to run.
+pub use rustc_hash::{FxHashMap, FxHashSet};
+
+/// A puzzle state drawn as text, one line per row of the grid.  Tests
+/// snapshot these with `insta` rather than spelling boards out by hand.
+pub trait Render {
+    fn render(&self) -> String;
+}
+
+/// A puzzle state that moves forward one tick at a time: robots drifting,
+/// a machine executing, a guard walking.
+pub trait Simulate {
+    fn step(&mut self);
+
+    fn run_n(&mut self, n: usize) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+
+    /// Steps until `done` holds, checking before each step, and returns
+    /// the number of steps taken.  Doesn't return if `done` never holds.
+    fn run_until(&mut self, mut done: impl FnMut(&Self) -> bool) -> usize {
+        let mut steps = 0;
+        while !done(self) {
+            self.step();
+            steps += 1;
+        }
+        steps
+    }
+
+    /// Every state from this one on, starting with this one.
+    fn iterate(self) -> Iterate<Self>
+    where
+        Self: Clone + Sized,
+    {
+        Iterate { state: self }
+    }
+}
+
+/// The endless iterator from `Simulate::iterate`.
+pub struct Iterate<S> {
+    state: S,
+}
+
+impl<S: Simulate + Clone> Iterator for Iterate<S> {
+    type Item = S;
+
+    fn next(&mut self) -> Option<S> {
+        let current = self.state.clone();
+        self.state.step();
+        Some(current)
+    }
+}
+
+/// Runs of neighboring elements from any iterator, for puzzles about the
+/// differences between one reading and the next.
+pub trait Pairwise: Iterator + Sized {
+    /// Each element with the one after it: `a, b, c` gives `(a, b)` and
+    /// `(b, c)`.
+    fn pairwise(self) -> Pairs<Self>
+    where
+        Self::Item: Clone,
+    {
+        Pairs {
+            iter: self,
+            last: None,
+        }
+    }
+
+    /// Each run of `N` consecutive elements, like `slice::windows` but for
+    /// any iterator.
+    fn windows_n<const N: usize>(self) -> WindowsN<Self, N>
+    where
+        Self::Item: Clone,
+    {
+        WindowsN {
+            iter: self,
+            window: VecDeque::with_capacity(N),
+        }
+    }
+}
+
+impl<I: Iterator> Pairwise for I {}
+
+/// The iterator from `Pairwise::pairwise`.
+pub struct Pairs<I: Iterator> {
+    iter: I,
+    last: Option<I::Item>,
+}
+
+impl<I: Iterator> Iterator for Pairs<I>
+where
+    I::Item: Clone,
+{
+    type Item = (I::Item, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = match self.last.take() {
+            Some(last) => last,
+            None => self.iter.next()?,
+        };
+        let second = self.iter.next()?;
+        self.last = Some(second.clone());
+        Some((first, second))
+    }
+}
+
+/// The iterator from `Pairwise::windows_n`.
+pub struct WindowsN<I: Iterator, const N: usize> {
+    iter: I,
+    window: VecDeque<I::Item>,
+}
+
+impl<I: Iterator, const N: usize> Iterator for WindowsN<I, N>
+where
+    I::Item: Clone,
+{
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.window.len() == N {
+            self.window.pop_front();
+        }
+        while self.window.len() < N {
+            self.window.push_back(self.iter.next()?);
+        }
+        Some(std::array::from_fn(|i| self.window[i].clone()))
+    }
+}
+
+/// Every pair of items at different positions, each pair once, with the
+/// earlier item first.
+pub fn unordered_pairs<T>(items: &[T]) -> impl Iterator<Item = (&T, &T)> {
+    items
+        .iter()
+        .enumerate()
+        .flat_map(move |(i, a)| items[i + 1..].iter().map(move |b| (a, b)))
+}
+
+/// Every way to choose `k` items, keeping their order, in lexicographic
+/// order of position.  Items at different positions count as different
+/// even if they're equal.
+pub fn combinations<T>(items: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations {
+        items,
+        indices: (k <= items.len()).then(|| (0..k).collect()),
+    }
+}
+
+/// The iterator from `combinations`.
+pub struct Combinations<'a, T> {
+    items: &'a [T],
+    /// The positions of the next combination, or None when done.
+    indices: Option<Vec<usize>>,
+}
+
+impl<'a, T> Iterator for Combinations<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let indices = self.indices.as_mut()?;
+        let result = indices.iter().map(|&i| &self.items[i]).collect();
+
+        // Bump the rightmost position that has room, and pack the rest
+        // in right after it.
+        let (n, k) = (self.items.len(), indices.len());
+        match (0..k).rev().find(|&i| indices[i] < n - k + i) {
+            Some(i) => {
+                indices[i] += 1;
+                for j in i + 1..k {
+                    indices[j] = indices[j - 1] + 1;
+                }
+            }
+            None => self.indices = None,
+        }
+        Some(result)
+    }
+}
+
+/// Every ordering of `items`, in lexicographic order of position.  Items
+/// at different positions count as different even if they're equal, so
+/// there are always `n!`.
+pub fn permutations<T>(items: &[T]) -> Permutations<'_, T> {
+    Permutations {
+        items,
+        indices: Some((0..items.len()).collect()),
+    }
+}
+
+/// The iterator from `permutations`.
+pub struct Permutations<'a, T> {
+    items: &'a [T],
+    /// The positions of the next permutation, or None when done.
+    indices: Option<Vec<usize>>,
+}
+
+impl<'a, T> Iterator for Permutations<'a, T> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let indices = self.indices.as_mut()?;
+        let result = indices.iter().map(|&i| &self.items[i]).collect();
+
+        // The usual next permutation: find the last ascent, swap its
+        // start with the least larger position after it, and reverse
+        // the tail.
+        match (1..indices.len())
+            .rev()
+            .find(|&i| indices[i - 1] < indices[i])
+        {
+            Some(i) => {
+                let pivot = i - 1;
+                let successor = (i..indices.len())
+                    .rev()
+                    .find(|&j| indices[j] > indices[pivot])
+                    .expect("The ascent itself is larger");
+                indices.swap(pivot, successor);
+                indices[i..].reverse();
+            }
+            None => self.indices = None,
+        }
+        Some(result)
+    }
+}
+
+/// A function that remembers its answers, for recursive counting.  The
+/// function gets itself, memoized, to make its recursive calls through:
+///
+/// ```
+/// let mut fib = advent_2024::Memo::new(|fib: &mut dyn FnMut(u64) -> u64, n: u64| {
+///     if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+/// });
+/// assert_eq!(fib.get(90), 2880067194370816120);
+/// ```
+pub struct Memo<K, V, F> {
+    cache: FxHashMap<K, V>,
+    f: F,
+}
+
+impl<K, V, F> Memo<K, V, F>
+where
+    K: std::hash::Hash + Eq + Clone,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    pub fn new(f: F) -> Self {
+        Self {
+            cache: FxHashMap::default(),
+            f,
+        }
+    }
+
+    pub fn get(&mut self, key: K) -> V {
+        Self::get_with(&mut self.cache, &self.f, key)
+    }
+
+    /// The number of answers remembered so far.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    fn get_with(cache: &mut FxHashMap<K, V>, f: &F, key: K) -> V {
+        if let Some(value) = cache.get(&key) {
+            return value.clone();
+        }
+        let value = f(&mut |key| Self::get_with(cache, f, key), key.clone());
+        cache.insert(key, value.clone());
+        value
+    }
+}
+
+/// Where the states from `initial`, each following from the last by
+/// `step`, start repeating: the number of states before the cycle, and the
+/// length of the cycle.  Uses Brent's algorithm, so only a couple of
+/// states are kept at once, at the cost of calling `step` a few times per
+/// state.  Doesn't return if the states never repeat.
+pub fn find_cycle<S: Eq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> (usize, usize) {
+    let cycle_len = cycle_len(initial.clone(), &mut step);
+
+    // Then two walkers a cycle apart first meet where the cycle starts.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..cycle_len {
+        hare = step(&hare);
+    }
+    let mut prefix_len = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix_len += 1;
+    }
+    (prefix_len, cycle_len)
+}
+
+/// The length of the cycle the states from `initial` fall into, without
+/// finding where it starts: the first half of `find_cycle`, for when only
+/// the length matters.  Stops as soon as a state comes round again.
+pub fn cycle_len<S: Eq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> usize {
+    // The hare runs ahead until it meets the tortoise, which jumps to the
+    // hare at every power of two, so the hare's run ends up the length of
+    // the cycle.
+    let mut power = 1;
+    let mut cycle_len = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == cycle_len {
+            tortoise = hare.clone();
+            power *= 2;
+            cycle_len = 0;
+        }
+        hare = step(&hare);
+        cycle_len += 1;
+    }
+    cycle_len
+}
+
+/// The first index in `0..len` where `pred` fails, or `len` if it never
+/// does.  Like `slice::partition_point`, but over indices rather than a
+/// slice, so the predicate can do real work like rerunning a search.
+/// `pred` must hold for a prefix of the range and fail for the rest.
+pub fn partition_point(len: usize, mut pred: impl FnMut(usize) -> bool) -> usize {
+    let mut start = 0;
+    let mut end = len;
+    // Invariant: `pred` holds below `start` and fails from `end` on.
+    while start < end {
+        let mid = start + (end - start) / 2;
+        if pred(mid) {
+            start = mid + 1;
+        } else {
+            end = mid;
+        }
+    }
+    start
+}
+
+/// The first index where `pred` fails, for when there's no upper bound to
+/// hand.  Doubles a probe until `pred` fails, then bisects the last
+/// stretch, so it calls `pred` about twice the log of the answer times.
+/// As with `partition_point`, `pred` must hold for a prefix of the
+/// indices; if it never fails, this never returns.
+pub fn exponential_search(mut pred: impl FnMut(usize) -> bool) -> usize {
+    let mut end = 1;
+    while pred(end - 1) {
+        end *= 2;
+    }
+    let start = end / 2;
+    start + partition_point(end - start, |i| pred(start + i))
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct TileIndex {
     pub width: usize,
@@ -21,9 +484,16 @@ pub const DIRECTIONS: [Direction; 4] = [
 
 impl Direction {
     pub fn is_horizontal(&self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    /// The change in row and column for one step this way.
+    pub fn delta(self) -> (isize, isize) {
         match self {
-            Direction::Left | Direction::Right => true,
-            _ => false,
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
         }
     }
 
@@ -44,22 +514,201 @@ impl Direction {
             Direction::Left => Direction::Down,
         }
     }
+
+    /// The arrow the puzzles draw for something facing this way.
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Left => '<',
+            Direction::Right => '>',
+            Direction::Up => '^',
+            Direction::Down => 'v',
+        }
+    }
+
+    /// The way back.
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+/// A set of directions, a bit each, for remembering which ways something
+/// has already gone through a tile.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy, Hash)]
+pub struct DirectionSet(u8);
+
+impl DirectionSet {
+    fn bit(dir: Direction) -> u8 {
+        1 << dir as u8
+    }
+
+    pub fn contains(self, dir: Direction) -> bool {
+        self.0 & Self::bit(dir) != 0
+    }
+
+    /// Adds `dir`, returning whether it wasn't already there.
+    pub fn insert(&mut self, dir: Direction) -> bool {
+        let new = !self.contains(dir);
+        self.0 |= Self::bit(dir);
+        new
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+/// A direction including the diagonals.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+/// Every `Direction8`, clockwise from `Up`.
+pub const DIRECTIONS8: [Direction8; 8] = [
+    Direction8::Up,
+    Direction8::UpRight,
+    Direction8::Right,
+    Direction8::DownRight,
+    Direction8::Down,
+    Direction8::DownLeft,
+    Direction8::Left,
+    Direction8::UpLeft,
+];
+
+impl Direction8 {
+    /// The change in row and column for one step this way.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction8::Up => (-1, 0),
+            Direction8::UpRight => (-1, 1),
+            Direction8::Right => (0, 1),
+            Direction8::DownRight => (1, 1),
+            Direction8::Down => (1, 0),
+            Direction8::DownLeft => (1, -1),
+            Direction8::Left => (0, -1),
+            Direction8::UpLeft => (-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        let (row, col) = self.delta();
+        row != 0 && col != 0
+    }
+
+    /// Turns 45 degrees clockwise.
+    pub fn clock(self) -> Self {
+        DIRECTIONS8[(self as usize + 1) % 8]
+    }
+
+    /// Turns 45 degrees counterclockwise.
+    pub fn counterclock(self) -> Self {
+        DIRECTIONS8[(self as usize + 7) % 8]
+    }
+
+    /// The way back.
+    pub fn opposite(self) -> Self {
+        DIRECTIONS8[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::Left => Direction8::Left,
+            Direction::Right => Direction8::Right,
+            Direction::Up => Direction8::Up,
+            Direction::Down => Direction8::Down,
+        }
+    }
+}
+
+/// Something on a grid that faces a way: a position, by index, and a
+/// direction, as for a guard on patrol or a reindeer in a maze.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub struct Agent {
+    pub pos: usize,
+    pub dir: Direction,
+}
+
+impl Agent {
+    /// Moves one step the way it faces, unless that leaves the grid.
+    pub fn forward(&self, tiles: &TileIndex) -> Option<Self> {
+        Some(Self {
+            pos: tiles.dir_to(self.pos, self.dir)?,
+            ..*self
+        })
+    }
+
+    /// Turns right in place.
+    pub fn turn_clock(&self) -> Self {
+        Self {
+            dir: self.dir.clock(),
+            ..*self
+        }
+    }
+
+    /// Turns left in place.
+    pub fn turn_counterclock(&self) -> Self {
+        Self {
+            dir: self.dir.counterclock(),
+            ..*self
+        }
+    }
 }
 
+/// Reads a direction written as an arrow (`^v<>`), a letter (`UDLR`), or a
+/// compass point (`NSEW`, with north up).
 impl TryFrom<char> for Direction {
     type Error = String;
     fn try_from(ch: char) -> Result<Direction, String> {
         match ch {
-            '<' => Ok(Direction::Left),
-            '>' => Ok(Direction::Right),
-            '^' => Ok(Direction::Up),
-            'v' => Ok(Direction::Down),
+            '<' | 'L' | 'W' => Ok(Direction::Left),
+            '>' | 'R' | 'E' => Ok(Direction::Right),
+            '^' | 'U' | 'N' => Ok(Direction::Up),
+            'v' | 'D' | 'S' => Ok(Direction::Down),
             _ => Err(format!("Unknown direction: {:?}", ch)),
         }
     }
 }
 
 impl TileIndex {
+    /// The row and column of the tile at `index`.
+    pub fn to_rc(&self, index: usize) -> (usize, usize) {
+        (self.row(index), self.col(index))
+    }
+
+    /// The index of the tile at `row`, `col`.  `col` must be less than
+    /// `width`, or this names a tile on a later row.
+    pub fn from_rc(&self, row: usize, col: usize) -> usize {
+        debug_assert!(col < self.width, "column {} is off the grid", col);
+        row * self.width + col
+    }
+
+    pub fn row(&self, index: usize) -> usize {
+        index / self.width
+    }
+
+    pub fn col(&self, index: usize) -> usize {
+        index % self.width
+    }
+
+    /// Whether `index` names a tile on the grid.
+    pub fn contains(&self, index: usize) -> bool {
+        index < self.width * self.height
+    }
+
     pub fn dir_to(&self, index: usize, dir: Direction) -> Option<usize> {
         match dir {
             Direction::Left => self.left(index),
@@ -70,7 +719,7 @@ impl TileIndex {
     }
 
     pub fn right(&self, index: usize) -> Option<usize> {
-        if index % self.width + 1 < self.width && index + 1 < (self.width * self.height) {
+        if self.col(index) + 1 < self.width && index + 1 < (self.width * self.height) {
             Some(index + 1)
         } else {
             None
@@ -78,7 +727,7 @@ impl TileIndex {
     }
 
     pub fn left(&self, index: usize) -> Option<usize> {
-        if index % self.width > 0 && index != 0 {
+        if self.col(index) > 0 && index != 0 {
             Some(index - 1)
         } else {
             None
@@ -86,7 +735,7 @@ impl TileIndex {
     }
 
     pub fn up(&self, index: usize) -> Option<usize> {
-        if index / self.width > 0 {
+        if self.row(index) > 0 {
             Some(index - self.width)
         } else {
             None
@@ -94,10 +743,1552 @@ impl TileIndex {
     }
 
     pub fn down(&self, index: usize) -> Option<usize> {
-        if index / self.width < self.height - 1 {
+        if self.row(index) < self.height - 1 {
             Some(index + self.width)
         } else {
             None
         }
     }
+
+    /// The tiles up, down, left and right of `index` that are on the grid,
+    /// each with the direction to it.
+    pub fn neighbors4(&self, index: usize) -> impl Iterator<Item = (usize, Direction)> + '_ {
+        DIRECTIONS
+            .into_iter()
+            .filter_map(move |dir| Some((self.dir_to(index, dir)?, dir)))
+    }
+
+    /// The index one step from `index` in `dir`, if that's on the grid.
+    pub fn dir8_to(&self, index: usize, dir: Direction8) -> Option<usize> {
+        let (row, col) = self.to_rc(index);
+        let (delta_row, delta_col) = dir.delta();
+        let row = row
+            .checked_add_signed(delta_row)
+            .filter(|&r| r < self.height)?;
+        let col = col
+            .checked_add_signed(delta_col)
+            .filter(|&c| c < self.width)?;
+        Some(self.from_rc(row, col))
+    }
+
+    /// Like `neighbors4`, but including the diagonals.
+    pub fn neighbors8(&self, index: usize) -> impl Iterator<Item = (usize, Direction8)> + '_ {
+        DIRECTIONS8
+            .into_iter()
+            .filter_map(move |dir| Some((self.dir8_to(index, dir)?, dir)))
+    }
+
+    /// Every tile on the grid within Manhattan distance `k` of `index`,
+    /// including `index` itself, each with its distance.
+    pub fn within_manhattan(
+        &self,
+        index: usize,
+        k: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let (row, col) = self.to_rc(index);
+        let rows = row.saturating_sub(k)..(row + k + 1).min(self.height);
+        rows.flat_map(move |r| {
+            let remaining = k - r.abs_diff(row);
+            let cols = col.saturating_sub(remaining)..(col + remaining + 1).min(self.width);
+            cols.map(move |c| (self.from_rc(r, c), r.abs_diff(row) + c.abs_diff(col)))
+        })
+    }
+
+    /// The tiles around the edge of the grid, each with the side it's on:
+    /// the top row going `Up`, then the bottom row, the left column and
+    /// the right column.  Corners come up once for each of their sides.
+    pub fn border(&self) -> impl Iterator<Item = (usize, Direction)> + '_ {
+        self.border_of(0, 0, self.width, self.height)
+    }
+
+    /// Like `border`, but around the `width` by `height` block whose
+    /// top-left tile is at `row`, `col`.
+    pub fn border_of(
+        &self,
+        row: usize,
+        col: usize,
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = (usize, Direction)> + '_ {
+        let index = move |r: usize, c: usize| self.from_rc(row + r, col + c);
+        let (last_row, last_col) = (height.saturating_sub(1), width.saturating_sub(1));
+        let rows = if width > 0 { 0..height } else { 0..0 };
+        let cols = if height > 0 { 0..width } else { 0..0 };
+        let top = cols.clone().map(move |c| (index(0, c), Direction::Up));
+        let bottom = cols.map(move |c| (index(last_row, c), Direction::Down));
+        let left = rows.clone().map(move |r| (index(r, 0), Direction::Left));
+        let right = rows.map(move |r| (index(r, last_col), Direction::Right));
+        top.chain(bottom).chain(left).chain(right)
+    }
+}
+
+/// A rectangle of tiles stored row by row, addressed by flat index like
+/// `TileIndex`.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    pub tiles: TileIndex,
+}
+
+impl<T> Grid<T> {
+    /// Wraps `data`, which must hold exactly one value per tile.
+    pub fn new(data: Vec<T>, tiles: TileIndex) -> Self {
+        assert_eq!(data.len(), tiles.width * tiles.height);
+        Self { data, tiles }
+    }
+
+    /// Builds a `width` by `height` grid with `f` giving the tile at each
+    /// index.
+    pub fn from_fn(width: usize, height: usize, f: impl FnMut(usize) -> T) -> Self {
+        Self {
+            data: (0..width * height).map(f).collect(),
+            tiles: TileIndex { width, height },
+        }
+    }
+
+    /// Reads one tile per character, one row per line, ignoring whitespace
+    /// around the input and around each line.  Fails if there are no tiles
+    /// or the lines aren't all the same width.
+    pub fn parse_chars(s: &str, mut f: impl FnMut(char) -> T) -> Result<Self, String> {
+        let mut data = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for (n, line) in s.trim().lines().enumerate() {
+            let line = line.trim();
+            let line_width = line.chars().count();
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => {
+                    return Err(format!(
+                        "Line {} is {} wide, but line 1 is {} wide",
+                        n + 1,
+                        line_width,
+                        width
+                    ));
+                }
+                Some(_) => {}
+            }
+            data.extend(line.chars().map(&mut f));
+            height += 1;
+        }
+        match width {
+            Some(width) if width > 0 => Ok(Self {
+                data,
+                tiles: TileIndex { width, height },
+            }),
+            _ => Err("Empty grid".to_string()),
+        }
+    }
+
+    /// A grid of the same shape with `f` applied to every tile.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            tiles: self.tiles.clone(),
+        }
+    }
+
+    /// Gives up the tiles, row by row.
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The tile at `index`, if that's on the grid.
+    pub fn get_index(&self, index: usize) -> Option<&T> {
+        self.data.get(index)
+    }
+
+    /// The tile at `row`, `col`, if that's on the grid.
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        let index = self.index_of(row, col)?;
+        Some(&self.data[index])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        let index = self.index_of(row, col)?;
+        Some(&mut self.data[index])
+    }
+
+    fn index_of(&self, row: usize, col: usize) -> Option<usize> {
+        (row < self.tiles.height && col < self.tiles.width).then(|| self.tiles.from_rc(row, col))
+    }
+
+    /// Like `index_of`, but a position off the grid is a bug.
+    fn expect_index_of(&self, row: usize, col: usize) -> usize {
+        self.index_of(row, col).unwrap_or_else(|| {
+            panic!(
+                "({}, {}) is off the {}x{} grid",
+                row, col, self.tiles.width, self.tiles.height
+            )
+        })
+    }
+
+    pub fn set(&mut self, index: usize, value: T) {
+        self.data[index] = value;
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.data.chunks(self.tiles.width)
+    }
+
+    pub fn iter_with_index(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.data.iter().enumerate()
+    }
+
+    /// The index of the first tile, row by row, for which `pred` holds.
+    pub fn position(&self, pred: impl FnMut(&T) -> bool) -> Option<usize> {
+        self.data.iter().position(pred)
+    }
+
+    /// The index of the first tile, row by row, equal to `value`.
+    pub fn position_of(&self, value: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.position(|t| t == value)
+    }
+
+    /// The indices of every tile for which `pred` holds, row by row.
+    pub fn find_all<'a>(
+        &'a self,
+        mut pred: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = usize> + 'a {
+        self.iter_with_index()
+            .filter(move |(_, t)| pred(t))
+            .map(|(index, _)| index)
+    }
+
+    /// Every `width` by `height` block of tiles that fits on the grid, with
+    /// the index of its top-left tile, row by row.
+    pub fn windows(
+        &self,
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = (usize, GridView<'_, T>)> + '_ {
+        let rows = (self.tiles.height + 1).saturating_sub(height);
+        let cols = (self.tiles.width + 1).saturating_sub(width);
+        (0..rows).flat_map(move |row| {
+            (0..cols).map(move |col| {
+                let view = GridView {
+                    grid: self,
+                    row,
+                    col,
+                    width,
+                    height,
+                };
+                (self.tiles.from_rc(row, col), view)
+            })
+        })
+    }
+
+    /// The indices of the tiles up, down, left and right of `index` that
+    /// are on the grid.
+    pub fn neighbors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.tiles.neighbors4(index).map(|(next, _)| next)
+    }
+
+    /// Labels the regions of the grid: tiles are in the same region when a
+    /// path of `same` neighbors, up, down, left or right, connects them.
+    pub fn components(&self, same: impl Fn(&T, &T) -> bool) -> Components {
+        const UNLABELED: usize = usize::MAX;
+        let mut labels = vec![UNLABELED; self.len()];
+        let mut members = Vec::new();
+        for start in 0..self.len() {
+            if labels[start] != UNLABELED {
+                continue;
+            }
+            let id = members.len();
+            let mut region = Vec::new();
+            labels[start] = id;
+            let mut queue = vec![start];
+            while let Some(index) = queue.pop() {
+                region.push(index);
+                for next in self.neighbors(index) {
+                    if labels[next] == UNLABELED && same(&self.data[index], &self.data[next]) {
+                        labels[next] = id;
+                        queue.push(next);
+                    }
+                }
+            }
+            members.push(region);
+        }
+        Components { labels, members }
+    }
+}
+
+/// The connected regions of a grid, from `Grid::components`.  Regions are
+/// numbered from 0 in order of their first tile.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Components {
+    /// The region of each tile, by index.
+    pub labels: Vec<usize>,
+    /// The tiles of each region, by region.
+    pub members: Vec<Vec<usize>>,
+}
+
+impl Components {
+    /// The number of regions.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// The number of tiles in region `id`.
+    pub fn size(&self, id: usize) -> usize {
+        self.members[id].len()
+    }
+}
+
+impl<T> Grid<T> {
+    /// Draws the grid one line per row, with `f` picking each tile's
+    /// character.
+    pub fn render_with(&self, f: impl FnMut(&T) -> char) -> String {
+        self.render_marked(f, [], ' ')
+    }
+
+    /// Like `render_with`, but draws `mark` over the tiles at `marked`, for
+    /// picking out a path or a region.
+    pub fn render_marked(
+        &self,
+        f: impl FnMut(&T) -> char,
+        marked: impl IntoIterator<Item = usize>,
+        mark: char,
+    ) -> String {
+        let mut chars: Vec<char> = self.data.iter().map(f).collect();
+        for index in marked {
+            chars[index] = mark;
+        }
+        let mut result = String::with_capacity(chars.len() + self.tiles.height);
+        for row in chars.chunks(self.tiles.width) {
+            result.extend(row);
+            result.push('\n');
+        }
+        result
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// The tiles that differ in `after`, as `(index, before, after)`, in
+    /// index order.  Panics if the grids aren't the same shape.
+    pub fn diff<'a>(
+        &'a self,
+        after: &'a Grid<T>,
+    ) -> impl Iterator<Item = (usize, &'a T, &'a T)> + 'a {
+        assert_eq!(
+            self.tiles, after.tiles,
+            "Can't diff grids of different shapes"
+        );
+        self.data
+            .iter()
+            .zip(&after.data)
+            .enumerate()
+            .filter(|(_, (before, after))| before != after)
+            .map(|(index, (before, after))| (index, before, after))
+    }
+
+    /// Draws this grid and `after` side by side, each row followed by a
+    /// `*` under every column that changed.
+    pub fn render_diff(&self, after: &Grid<T>, mut f: impl FnMut(&T) -> char) -> String {
+        let mut marks = vec![' '; self.len()];
+        for (index, _, _) in self.diff(after) {
+            marks[index] = '*';
+        }
+        let mut result = String::new();
+        for ((before, after), marks) in self
+            .rows()
+            .zip(after.rows())
+            .zip(marks.chunks(self.tiles.width))
+        {
+            result.extend(before.iter().map(&mut f));
+            result.push_str(" | ");
+            result.extend(after.iter().map(&mut f));
+            result.push_str(" | ");
+            result.extend(marks);
+            result.truncate(result.trim_end().len());
+            result.push('\n');
+        }
+        result
+    }
+}
+
+impl Render for Grid<char> {
+    fn render(&self) -> String {
+        self.render_with(|&ch| ch)
+    }
+}
+
+impl<T> std::ops::Index<usize> for Grid<T> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
+        &self.data[index]
+    }
+}
+
+impl<T> std::ops::IndexMut<usize> for Grid<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.data[index]
+    }
+}
+
+/// A rectangular block of a `Grid`, addressed by `(row, col)` from its own
+/// top-left corner.
+#[derive(Debug, Clone, Copy)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    row: usize,
+    col: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn get(&self, row: usize, col: usize) -> Option<&'a T> {
+        if row < self.height && col < self.width {
+            self.grid.get(self.row + row, self.col + col)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> std::ops::Index<(usize, usize)> for GridView<'_, T> {
+    type Output = T;
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        self.get(row, col).unwrap_or_else(|| {
+            panic!(
+                "({}, {}) is off the {}x{} view",
+                row, col, self.width, self.height
+            )
+        })
+    }
+}
+
+/// Indexing by `(row, col)`, which panics off the grid; see `Grid::get` for
+/// the checked version.
+impl<T> std::ops::Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        &self.data[self.expect_index_of(row, col)]
+    }
+}
+
+impl<T> std::ops::IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        let index = self.expect_index_of(row, col);
+        &mut self.data[index]
+    }
+}
+
+/// A grid of booleans packed 64 to a word, for occupancy maps that get
+/// cloned or combined a lot.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    pub tiles: TileIndex,
+}
+
+impl BitGrid {
+    /// A grid with every tile clear.
+    pub fn new(tiles: TileIndex) -> Self {
+        Self {
+            words: vec![0; (tiles.width * tiles.height).div_ceil(64)],
+            tiles,
+        }
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        assert!(self.tiles.contains(index), "{} is off the grid", index);
+        self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    pub fn set(&mut self, index: usize) {
+        assert!(self.tiles.contains(index), "{} is off the grid", index);
+        self.words[index / 64] |= 1 << (index % 64);
+    }
+
+    pub fn clear(&mut self, index: usize) {
+        assert!(self.tiles.contains(index), "{} is off the grid", index);
+        self.words[index / 64] &= !(1 << (index % 64));
+    }
+
+    /// How many tiles are set.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// The indices of the set tiles, in order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+
+    /// Sets every tile that's set in `other`, which must be the same shape.
+    pub fn union_with(&mut self, other: &BitGrid) {
+        assert_eq!(self.tiles, other.tiles);
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word |= other;
+        }
+    }
+
+    /// Clears every tile that's clear in `other`, which must be the same
+    /// shape.
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        assert_eq!(self.tiles, other.tiles);
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word &= other;
+        }
+    }
+}
+
+/// A signed point or offset `(x, y)`, with `y` growing downward like rows
+/// do, for puzzles that work in unbounded or wrapping coordinates.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, PartialOrd, Ord, Hash, Default)]
+pub struct Vec2(pub i64, pub i64);
+
+impl Vec2 {
+    /// Wraps into the `width` by `height` rectangle at the origin, as on a
+    /// torus.
+    pub fn rem_euclid(self, width: i64, height: i64) -> Self {
+        Self(self.0.rem_euclid(width), self.1.rem_euclid(height))
+    }
+
+    /// The Manhattan length.
+    pub fn manhattan(self) -> i64 {
+        self.0.abs() + self.1.abs()
+    }
+
+    pub fn manhattan_to(self, other: Vec2) -> i64 {
+        (other - self).manhattan()
+    }
+
+    /// The point at `index`: its column and row.
+    pub fn from_index(tiles: &TileIndex, index: usize) -> Self {
+        let (row, col) = tiles.to_rc(index);
+        Self(col as i64, row as i64)
+    }
+
+    /// The index of this point, if it's on the grid.
+    pub fn to_index(self, tiles: &TileIndex) -> Option<usize> {
+        let col = usize::try_from(self.0).ok().filter(|&c| c < tiles.width)?;
+        let row = usize::try_from(self.1).ok().filter(|&r| r < tiles.height)?;
+        Some(tiles.from_rc(row, col))
+    }
+
+    /// The point one step away in `dir`; the same as `self + dir`.
+    pub fn step(self, dir: Direction) -> Self {
+        self + Vec2::from(dir)
+    }
+
+    /// The point one step away in `dir`, if that's still on the grid.
+    pub fn checked_step(self, dir: Direction, tiles: &TileIndex) -> Option<Self> {
+        let next = self.step(dir);
+        next.to_index(tiles).map(|_| next)
+    }
+}
+
+impl std::ops::Add for Vec2 {
+    type Output = Vec2;
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2(self.0 + other.0, self.1 + other.1)
+    }
+}
+
+impl std::ops::Add<&Vec2> for Vec2 {
+    type Output = Vec2;
+    fn add(self, other: &Vec2) -> Vec2 {
+        self + *other
+    }
+}
+
+impl std::ops::AddAssign for Vec2 {
+    fn add_assign(&mut self, other: Vec2) {
+        *self = *self + other;
+    }
+}
+
+impl std::ops::Sub for Vec2 {
+    type Output = Vec2;
+    fn sub(self, other: Vec2) -> Vec2 {
+        Vec2(self.0 - other.0, self.1 - other.1)
+    }
+}
+
+impl std::ops::Sub<&Vec2> for Vec2 {
+    type Output = Vec2;
+    fn sub(self, other: &Vec2) -> Vec2 {
+        self - *other
+    }
+}
+
+impl std::ops::SubAssign for Vec2 {
+    fn sub_assign(&mut self, other: Vec2) {
+        *self = *self - other;
+    }
+}
+
+impl std::ops::Neg for Vec2 {
+    type Output = Vec2;
+    fn neg(self) -> Vec2 {
+        Vec2(-self.0, -self.1)
+    }
+}
+
+impl std::ops::Mul<i64> for Vec2 {
+    type Output = Vec2;
+    fn mul(self, k: i64) -> Vec2 {
+        Vec2(self.0 * k, self.1 * k)
+    }
+}
+
+impl std::ops::Mul<Vec2> for i64 {
+    type Output = Vec2;
+    fn mul(self, v: Vec2) -> Vec2 {
+        v * self
+    }
+}
+
+/// The unit step in a direction.
+impl From<Direction> for Vec2 {
+    fn from(dir: Direction) -> Self {
+        let (delta_row, delta_col) = dir.delta();
+        Vec2(delta_col as i64, delta_row as i64)
+    }
+}
+
+impl From<(i64, i64)> for Vec2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Vec2(x, y)
+    }
+}
+
+impl std::ops::Add<Direction> for Vec2 {
+    type Output = Vec2;
+    fn add(self, dir: Direction) -> Vec2 {
+        self.step(dir)
+    }
+}
+
+/// A position on a `SparseGrid`: `(x, y)`, with `y` growing downward.
+pub type Pos = (isize, isize);
+
+/// Tiles at signed positions, for maps that are unbounded or too big to
+/// store as a `Grid`.  Positions without a tile are simply empty.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct SparseGrid<T> {
+    tiles: FxHashMap<Pos, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            tiles: FxHashMap::default(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.tiles.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.tiles.get_mut(&pos)
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.tiles.contains_key(&pos)
+    }
+
+    /// Puts `value` at `pos`, returning what was there.
+    pub fn insert(&mut self, pos: Pos, value: T) -> Option<T> {
+        self.tiles.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Pos) -> Option<T> {
+        self.tiles.remove(&pos)
+    }
+
+    /// The tiles, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.tiles.iter().map(|(&pos, t)| (pos, t))
+    }
+
+    /// The position one step from `pos` in `dir`.  Unlike
+    /// `TileIndex::dir_to`, there's no edge to fall off.
+    pub fn dir_to(&self, (x, y): Pos, dir: Direction) -> Pos {
+        let (delta_row, delta_col) = dir.delta();
+        (x + delta_col, y + delta_row)
+    }
+
+    /// The positions up, down, left and right of `pos` that hold a tile.
+    pub fn neighbors(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS
+            .into_iter()
+            .map(move |dir| self.dir_to(pos, dir))
+            .filter(|next| self.contains(*next))
+    }
+
+    /// The smallest and largest corners of a rectangle holding every tile,
+    /// or `None` if there are none.
+    pub fn bounds(&self) -> Option<(Pos, Pos)> {
+        let mut positions = self.tiles.keys();
+        let &first = positions.next()?;
+        Some(
+            positions.fold((first, first), |((x0, y0), (x1, y1)), &(x, y)| {
+                ((x0.min(x), y0.min(y)), (x1.max(x), y1.max(y)))
+            }),
+        )
+    }
+}
+
+impl<T> FromIterator<(Pos, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Pos, T)>>(iter: I) -> Self {
+        Self {
+            tiles: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Splits `input` into the sections between blank lines, one for each of
+/// `names`, which name the sections in error messages.  Runs of blank
+/// lines count as one, and blank lines at either end are ignored, as are
+/// trailing spaces and `\r`s on each section.
+///
+/// ```
+/// # use advent_2024::split_sections;
+/// let [rules, updates] = split_sections("1|2\n\n1,2\n", ["rules", "updates"])?;
+/// assert_eq!((rules, updates), ("1|2", "1,2"));
+/// assert_eq!(
+///     split_sections("1|2\n", ["rules", "updates"]),
+///     Err("Missing updates section: expected 2 sections separated by blank lines, found 1"
+///         .to_string())
+/// );
+/// # Ok::<(), String>(())
+/// ```
+pub fn split_sections<'a, const N: usize>(
+    input: &'a str,
+    names: [&str; N],
+) -> Result<[&'a str; N], String> {
+    let mut sections = Vec::new();
+    let mut start = None;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        match (line.trim().is_empty(), start) {
+            (false, None) => start = Some(offset),
+            (true, Some(from)) => {
+                sections.push(input[from..offset].trim_end());
+                start = None;
+            }
+            _ => {}
+        }
+        offset += line.len();
+    }
+    if let Some(from) = start {
+        sections.push(input[from..].trim_end());
+    }
+
+    if sections.len() > N {
+        return Err(format!(
+            "Expected {} sections separated by blank lines, found {}",
+            N,
+            sections.len()
+        ));
+    }
+    let mut result = [""; N];
+    for (i, name) in names.iter().enumerate() {
+        result[i] = sections.get(i).ok_or_else(|| {
+            format!(
+                "Missing {} section: expected {} sections separated by blank lines, found {}",
+                name,
+                N,
+                sections.len()
+            )
+        })?;
+    }
+    Ok(result)
+}
+
+/// Parses each nonblank line of `input` as `N` points written `x,y`,
+/// separated by spaces.  Each point may have a label, as in `p=3,4`, which
+/// is ignored.  Errors name the line that didn't parse.
+///
+/// ```
+/// # use advent_2024::parse_points;
+/// let robots = parse_points::<i64, 2>("p=0,4 v=3,-3\np=6,3 v=-1,-3\n")?;
+/// assert_eq!(robots[1], [(6, 3), (-1, -3)]);
+/// assert_eq!(
+///     parse_points::<u8, 1>("1,2\n3;4\n"),
+///     Err("Line 2: expected a point like x,y, found \"3;4\"".to_string())
+/// );
+/// # Ok::<(), String>(())
+/// ```
+pub fn parse_points<T: std::str::FromStr, const N: usize>(
+    input: &str,
+) -> Result<Vec<[(T, T); N]>, String> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() != N {
+                return Err(format!(
+                    "Line {}: expected {} points, found {:?}",
+                    i + 1,
+                    N,
+                    line
+                ));
+            }
+            let mut points = Vec::with_capacity(N);
+            for field in fields {
+                let coords = field.split_once('=').map_or(field, |(_, coords)| coords);
+                let point = coords
+                    .split_once(',')
+                    .and_then(|(x, y)| Some((x.parse().ok()?, y.parse().ok()?)))
+                    .ok_or_else(|| {
+                        format!(
+                            "Line {}: expected a point like x,y, found {:?}",
+                            i + 1,
+                            field
+                        )
+                    })?;
+                points.push(point);
+            }
+            Ok(points
+                .try_into()
+                .unwrap_or_else(|_| unreachable!("Checked there are {} points", N)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use proptest::prelude::*;
+
+    /// A grid of up to 40x40 tiles, and a tile on it.
+    fn tile() -> impl Strategy<Value = (TileIndex, usize)> {
+        (1..40usize, 1..40usize)
+            .prop_flat_map(|(width, height)| (Just(TileIndex { width, height }), 0..width * height))
+    }
+
+    fn direction() -> impl Strategy<Value = Direction> {
+        prop::sample::select(DIRECTIONS.to_vec())
+    }
+
+    #[gtest]
+    fn test_parse_direction() -> googletest::Result<()> {
+        for (spellings, dir) in [
+            ("^UN", Direction::Up),
+            ("vDS", Direction::Down),
+            ("<LW", Direction::Left),
+            (">RE", Direction::Right),
+        ] {
+            for ch in spellings.chars() {
+                verify_that!(Direction::try_from(ch), ok(eq(&dir)))?;
+            }
+        }
+        verify_that!(Direction::try_from('V'), err(eq("Unknown direction: 'V'")))
+    }
+
+    #[gtest]
+    fn test_border() -> googletest::Result<()> {
+        let tiles = TileIndex {
+            width: 3,
+            height: 2,
+        };
+        verify_that!(
+            tiles.border().collect::<Vec<_>>(),
+            eq(&vec![
+                (0, Direction::Up),
+                (1, Direction::Up),
+                (2, Direction::Up),
+                (3, Direction::Down),
+                (4, Direction::Down),
+                (5, Direction::Down),
+                (0, Direction::Left),
+                (3, Direction::Left),
+                (2, Direction::Right),
+                (5, Direction::Right),
+            ])
+        )?;
+        let tiles = TileIndex {
+            width: 4,
+            height: 4,
+        };
+        verify_that!(
+            tiles.border_of(1, 1, 2, 1).collect::<Vec<_>>(),
+            eq(&vec![
+                (5, Direction::Up),
+                (6, Direction::Up),
+                (5, Direction::Down),
+                (6, Direction::Down),
+                (5, Direction::Left),
+                (6, Direction::Right),
+            ])
+        )?;
+        verify_that!(tiles.border_of(1, 1, 0, 3).count(), eq(0))
+    }
+
+    #[gtest]
+    fn test_neighbors8() -> googletest::Result<()> {
+        let tiles = TileIndex {
+            width: 3,
+            height: 3,
+        };
+        verify_that!(tiles.neighbors8(4).count(), eq(8))?;
+        verify_that!(
+            tiles.neighbors8(0).collect::<Vec<_>>(),
+            eq(&vec![
+                (1, Direction8::Right),
+                (4, Direction8::DownRight),
+                (3, Direction8::Down)
+            ])
+        )?;
+        verify_that!(tiles.dir8_to(2, Direction8::UpRight), none())
+    }
+
+    #[gtest]
+    fn test_within_manhattan() -> googletest::Result<()> {
+        let tiles = TileIndex {
+            width: 4,
+            height: 3,
+        };
+        verify_that!(
+            tiles.within_manhattan(1, 1).collect::<Vec<_>>(),
+            unordered_elements_are![eq(&(1, 0)), eq(&(0, 1)), eq(&(2, 1)), eq(&(5, 1))]
+        )?;
+        verify_that!(
+            tiles.within_manhattan(5, 0).collect::<Vec<_>>(),
+            [eq(&(5, 0))]
+        )?;
+        verify_that!(tiles.within_manhattan(0, 10).count(), eq(12))
+    }
+
+    #[gtest]
+    fn test_grid_parse_chars() -> googletest::Result<()> {
+        let grid = Grid::parse_chars("\n  012\n  345\n", |ch| ch.to_digit(10).unwrap())
+            .into_test_result()?;
+        verify_that!(
+            grid.tiles,
+            eq(&TileIndex {
+                width: 3,
+                height: 2
+            })
+        )?;
+        verify_that!(grid.get_index(4), some(eq(&4)))?;
+        verify_that!(grid.get_index(6), none())?;
+        verify_that!(
+            grid.neighbors(1).collect::<Vec<_>>(),
+            unordered_elements_are![eq(&0), eq(&2), eq(&4)]
+        )
+    }
+
+    #[gtest]
+    fn test_grid_row_col() -> googletest::Result<()> {
+        let mut grid = Grid::parse_chars("abc\ndef", |ch| ch).into_test_result()?;
+        verify_that!(grid[(1, 0)], eq('d'))?;
+        verify_that!(grid.get(0, 2), some(eq(&'c')))?;
+        verify_that!(grid.get(0, 3), none())?;
+        verify_that!(grid.get(2, 0), none())?;
+        grid[(0, 1)] = 'B';
+        *grid.get_mut(1, 2).unwrap() = 'F';
+        verify_that!(grid.render(), eq("aBc\ndeF\n"))
+    }
+
+    #[test]
+    #[should_panic(expected = "(0, 3) is off the 3x2 grid")]
+    fn test_grid_row_col_off_grid() {
+        let grid = Grid::parse_chars("abc\ndef", |ch| ch).unwrap();
+        let _ = grid[(0, 3)];
+    }
+
+    #[gtest]
+    fn test_grid_from_fn_and_map() -> googletest::Result<()> {
+        let grid = Grid::from_fn(3, 2, |index| index * 10);
+        verify_that!(
+            grid.tiles,
+            eq(&TileIndex {
+                width: 3,
+                height: 2
+            })
+        )?;
+        verify_that!(grid[(1, 2)], eq(50))?;
+        let odd = grid.map(|&n| n % 20 != 0);
+        verify_that!(odd.tiles, eq(&grid.tiles))?;
+        verify_that!(
+            odd.into_vec(),
+            eq(&vec![false, true, false, true, false, true])
+        )
+    }
+
+    #[gtest]
+    fn test_grid_find() -> googletest::Result<()> {
+        let grid = Grid::parse_chars("S.#\n#.E", |ch| ch).into_test_result()?;
+        verify_that!(grid.position_of(&'E'), some(eq(5)))?;
+        verify_that!(grid.position_of(&'X'), none())?;
+        verify_that!(grid.position(|&ch| ch != 'S'), some(eq(1)))?;
+        verify_that!(grid.find_all(|&ch| ch == '#').collect::<Vec<_>>(), [&2, &3])
+    }
+
+    #[gtest]
+    fn test_grid_windows() -> googletest::Result<()> {
+        let grid = Grid::parse_chars("abcd\nefgh\nijkl", |ch| ch).into_test_result()?;
+        let windows: Vec<(usize, String)> = grid
+            .windows(3, 2)
+            .map(|(index, view)| {
+                let corners = [view[(0, 0)], view[(0, 2)], view[(1, 0)], view[(1, 2)]];
+                (index, corners.iter().collect())
+            })
+            .collect();
+        verify_that!(
+            windows,
+            eq(&vec![
+                (0, "aceg".to_string()),
+                (1, "bdfh".to_string()),
+                (4, "egik".to_string()),
+                (5, "fhjl".to_string()),
+            ])
+        )?;
+        let (_, view) = grid.windows(2, 2).next().unwrap();
+        verify_that!(view.get(0, 2), none())?;
+        verify_that!(grid.windows(5, 1).count(), eq(0))
+    }
+
+    #[gtest]
+    fn test_bit_grid() -> googletest::Result<()> {
+        let tiles = TileIndex {
+            width: 10,
+            height: 10,
+        };
+        let mut a = BitGrid::new(tiles.clone());
+        let mut b = BitGrid::new(tiles);
+        for index in [0, 63, 64, 99] {
+            a.set(index);
+        }
+        b.set(64);
+        b.set(70);
+        verify_that!(a.contains(63), eq(true))?;
+        verify_that!(a.contains(62), eq(false))?;
+        verify_that!(a.count(), eq(4))?;
+
+        let mut union = a.clone();
+        union.union_with(&b);
+        verify_that!(
+            union.iter().collect::<Vec<_>>(),
+            eq(&vec![0, 63, 64, 70, 99])
+        )?;
+        a.intersect_with(&b);
+        verify_that!(a.iter().collect::<Vec<_>>(), eq(&vec![64]))?;
+        a.clear(64);
+        verify_that!(a.count(), eq(0))
+    }
+
+    #[test]
+    #[should_panic(expected = "100 is off the grid")]
+    fn test_bit_grid_off_grid() {
+        let grid = BitGrid::new(TileIndex {
+            width: 10,
+            height: 10,
+        });
+        grid.contains(100);
+    }
+
+    #[gtest]
+    fn test_vec2() -> googletest::Result<()> {
+        verify_that!(Vec2(0, 4) + 2 * Vec2(3, -3), eq(Vec2(6, -2)))?;
+        verify_that!(Vec2(6, -2) - Vec2(1, 1) * 2, eq(Vec2(4, -4)))?;
+        verify_that!(-Vec2(1, -2), eq(Vec2(-1, 2)))?;
+        verify_that!(Vec2(12, -11).rem_euclid(11, 7), eq(Vec2(1, 3)))?;
+        verify_that!(Vec2(1, 1).manhattan_to(Vec2(-2, 5)), eq(7))?;
+        let tiles = TileIndex {
+            width: 4,
+            height: 3,
+        };
+        verify_that!(Vec2::from_index(&tiles, 6), eq(Vec2(2, 1)))?;
+        verify_that!(Vec2(2, 1).to_index(&tiles), some(eq(6)))?;
+        verify_that!(Vec2(4, 1).to_index(&tiles), none())?;
+        verify_that!(Vec2(-1, 1).to_index(&tiles), none())
+    }
+
+    #[gtest]
+    fn test_direction_set() -> googletest::Result<()> {
+        let mut set = DirectionSet::default();
+        verify_that!(set.is_empty(), is_true())?;
+        verify_that!(set.insert(Direction::Up), is_true())?;
+        verify_that!(set.insert(Direction::Up), is_false())?;
+        verify_that!(set.insert(Direction::Left), is_true())?;
+        verify_that!(
+            DIRECTIONS.map(|dir| set.contains(dir)),
+            eq([true, false, true, false])
+        )
+    }
+
+    #[gtest]
+    fn test_vec2_step() -> googletest::Result<()> {
+        let tiles = TileIndex {
+            width: 4,
+            height: 3,
+        };
+        verify_that!(Vec2(2, 1) + Direction::Up, eq(Vec2(2, 0)))?;
+        verify_that!(Vec2(2, 1).step(Direction::Left), eq(Vec2(1, 1)))?;
+        verify_that!(Vec2(0, 0).step(Direction::Left), eq(Vec2(-1, 0)))?;
+        verify_that!(
+            Vec2(3, 1).checked_step(Direction::Down, &tiles),
+            some(eq(Vec2(3, 2)))
+        )?;
+        verify_that!(Vec2(3, 1).checked_step(Direction::Right, &tiles), none())
+    }
+
+    #[derive(Debug, Clone)]
+    struct Counter(u32);
+
+    impl Simulate for Counter {
+        fn step(&mut self) {
+            self.0 += 1;
+        }
+    }
+
+    #[gtest]
+    fn test_simulate() -> googletest::Result<()> {
+        let mut counter = Counter(0);
+        counter.run_n(3);
+        verify_that!(counter.0, eq(3))?;
+        verify_that!(counter.run_until(|c| c.0 % 5 == 0), eq(2))?;
+        verify_that!(counter.run_until(|c| c.0 % 5 == 0), eq(0))?;
+        verify_that!(
+            counter.iterate().take(3).map(|c| c.0).collect::<Vec<_>>(),
+            eq(&vec![5, 6, 7])
+        )
+    }
+
+    #[gtest]
+    fn test_memo() -> googletest::Result<()> {
+        let mut paths = Memo::new(|paths: &mut dyn FnMut((u32, u32)) -> u64, (row, col)| {
+            if row == 0 || col == 0 {
+                1
+            } else {
+                paths((row - 1, col)) + paths((row, col - 1))
+            }
+        });
+        verify_that!(paths.get((2, 2)), eq(6))?;
+        verify_that!(paths.len(), eq(8))?;
+        verify_that!(paths.get((16, 16)), eq(601080390))
+    }
+
+    #[gtest]
+    fn test_partition_point() -> googletest::Result<()> {
+        verify_that!(partition_point(0, |_| panic!("empty range")), eq(0))?;
+        verify_that!(partition_point(10, |_| true), eq(10))?;
+        verify_that!(partition_point(10, |_| false), eq(0))?;
+        for len in 0..20 {
+            for split in 0..=len {
+                verify_that!(partition_point(len, |i| i < split), eq(split))?;
+            }
+        }
+        verify_that!(
+            partition_point(usize::MAX, |i| i < usize::MAX - 1),
+            eq(usize::MAX - 1)
+        )
+    }
+
+    #[gtest]
+    fn test_exponential_search() -> googletest::Result<()> {
+        for split in 0..100 {
+            let mut calls = 0;
+            let found = exponential_search(|i| {
+                calls += 1;
+                i < split
+            });
+            verify_that!(found, eq(split))?;
+            verify_that!(
+                calls,
+                le(2 * (usize::BITS - split.leading_zeros()) as usize + 2)
+            )?;
+        }
+        verify_that!(exponential_search(|i| i < 1 << 40), eq(1 << 40))
+    }
+
+    #[gtest]
+    fn test_find_cycle() -> googletest::Result<()> {
+        verify_that!(find_cycle(0u32, |&n| (n + 1) % 5), eq((0, 5)))?;
+        verify_that!(find_cycle(7u32, |&n| n.saturating_sub(1)), eq((7, 1)))?;
+        verify_that!(cycle_len(9u32, |&n| (n + 1) % 5), eq(5))?;
+        for modulus in [672u64, 1000, 1021, 4096] {
+            let step = |&n: &u64| (n * n + 1) % modulus;
+            let mut seen = FxHashMap::default();
+            let mut n = 3;
+            while !seen.contains_key(&n) {
+                seen.insert(n, seen.len());
+                n = step(&n);
+            }
+            let expected = (seen[&n], seen.len() - seen[&n]);
+            verify_that!(find_cycle(3, step), eq(expected))?;
+        }
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_agent() -> googletest::Result<()> {
+        let tiles = TileIndex {
+            width: 4,
+            height: 3,
+        };
+        let agent = Agent {
+            pos: 5,
+            dir: Direction::Up,
+        };
+        verify_that!(
+            agent.forward(&tiles),
+            some(eq(Agent {
+                pos: 1,
+                dir: Direction::Up
+            }))
+        )?;
+        verify_that!(
+            agent.forward(&tiles).and_then(|a| a.forward(&tiles)),
+            none()
+        )?;
+        verify_that!(agent.turn_clock().dir, eq(Direction::Right))?;
+        verify_that!(agent.turn_counterclock().dir, eq(Direction::Left))?;
+        verify_that!(
+            agent.turn_clock().turn_clock().turn_clock().turn_clock(),
+            eq(agent)
+        )
+    }
+
+    #[gtest]
+    fn test_grid_components() -> googletest::Result<()> {
+        let grid = Grid::parse_chars("AAB\nCAB\nCCA", |ch| ch).into_test_result()?;
+        let components = grid.components(|a, b| a == b);
+        verify_that!(components.len(), eq(4))?;
+        verify_that!(components.labels, eq(&vec![0, 0, 1, 2, 0, 1, 2, 2, 3]))?;
+        verify_that!(components.members[0], unordered_elements_are![&0, &1, &4])?;
+        verify_that!(
+            (0..components.len())
+                .map(|id| components.size(id))
+                .collect::<Vec<_>>(),
+            eq(&vec![3, 2, 3, 1])
+        )
+    }
+
+    #[gtest]
+    fn test_sparse_grid() -> googletest::Result<()> {
+        let mut grid: SparseGrid<char> = [((0, 0), 'a'), ((-5, 2), 'b')].into_iter().collect();
+        verify_that!(grid.len(), eq(2))?;
+        verify_that!(grid.get((-5, 2)), some(eq(&'b')))?;
+        verify_that!(grid.bounds(), some(eq(((-5, 0), (0, 2)))))?;
+        verify_that!(grid.insert((0, -1), 'c'), none())?;
+        verify_that!(grid.insert((0, -1), 'd'), some(eq('c')))?;
+        verify_that!(grid.dir_to((0, 0), Direction::Up), eq((0, -1)))?;
+        verify_that!(grid.neighbors((0, 0)).collect::<Vec<_>>(), [eq(&(0, -1))])?;
+        verify_that!(grid.remove((0, 0)), some(eq('a')))?;
+        verify_that!(grid.neighbors((0, 0)).count(), eq(1))?;
+        verify_that!(SparseGrid::<char>::new().bounds(), none())
+    }
+
+    #[gtest]
+    fn test_grid_render() -> googletest::Result<()> {
+        let grid = Grid::parse_chars("#.#\n...", |ch| ch == '#').into_test_result()?;
+        verify_that!(
+            grid.render_with(|&wall| if wall { '#' } else { '.' }),
+            eq("#.#\n...\n")
+        )?;
+        verify_that!(
+            grid.render_marked(|&wall| if wall { '#' } else { '.' }, [1, 4], 'O'),
+            eq("#O#\n.O.\n")
+        )?;
+        let chars = Grid::parse_chars(" ab\n cd\n", |ch| ch).into_test_result()?;
+        verify_that!(chars.render(), eq("ab\ncd\n"))
+    }
+
+    #[gtest]
+    fn test_grid_diff() -> googletest::Result<()> {
+        let before = Grid::parse_chars("#@O.\n#...", |ch| ch).into_test_result()?;
+        let after = Grid::parse_chars("#.@O\n#...", |ch| ch).into_test_result()?;
+        verify_that!(
+            before.diff(&after).collect::<Vec<_>>(),
+            eq(&vec![(1, &'@', &'.'), (2, &'O', &'@'), (3, &'.', &'O')])
+        )?;
+        verify_that!(before.diff(&before).count(), eq(0))?;
+        verify_that!(
+            before.render_diff(&after, |&ch| ch),
+            eq("#@O. | #.@O |  ***\n#... | #... |\n")
+        )
+    }
+
+    #[gtest]
+    fn test_grid_parse_chars_errors() -> googletest::Result<()> {
+        verify_that!(
+            Grid::parse_chars("012\n34\n567", |ch| ch),
+            err(eq("Line 2 is 2 wide, but line 1 is 3 wide"))
+        )?;
+        verify_that!(
+            Grid::parse_chars("012\n\n567", |ch| ch),
+            err(eq("Line 2 is 0 wide, but line 1 is 3 wide"))
+        )?;
+        verify_that!(Grid::parse_chars("  \n ", |ch| ch), err(eq("Empty grid")))
+    }
+
+    #[gtest]
+    fn test_split_sections() -> googletest::Result<()> {
+        verify_that!(
+            split_sections("\n##\n#.\n\n\n<>\n\n", ["map", "moves"]),
+            ok(eq(&["##\n#.", "<>"]))
+        )?;
+        verify_that!(
+            split_sections("a: 1\r\nb: 2\r\n\r\n3,4\r\n", ["registers", "program"]),
+            ok(eq(&["a: 1\r\nb: 2", "3,4"]))
+        )?;
+        verify_that!(
+            split_sections("", ["towels", "designs"]),
+            err(eq(
+                "Missing towels section: expected 2 sections separated by blank lines, found 0"
+            ))
+        )?;
+        verify_that!(
+            split_sections("a\n\nb\n\nc", ["first", "second"]),
+            err(eq("Expected 2 sections separated by blank lines, found 3"))
+        )
+    }
+
+    #[gtest]
+    fn test_pairwise() -> googletest::Result<()> {
+        verify_that!(
+            [1, 2, 4, 7].into_iter().pairwise().collect::<Vec<_>>(),
+            eq(&vec![(1, 2), (2, 4), (4, 7)])
+        )?;
+        verify_that!(std::iter::once(1).pairwise().count(), eq(0))?;
+        verify_that!(std::iter::empty::<u8>().pairwise().count(), eq(0))
+    }
+
+    #[gtest]
+    fn test_windows_n() -> googletest::Result<()> {
+        verify_that!(
+            "abcd".chars().windows_n::<3>().collect::<Vec<_>>(),
+            eq(&vec![['a', 'b', 'c'], ['b', 'c', 'd']])
+        )?;
+        verify_that!("ab".chars().windows_n::<3>().count(), eq(0))?;
+        // Agrees with the slice version.
+        let values = [3, 1, 4, 1, 5, 9, 2, 6];
+        verify_that!(
+            values.iter().copied().windows_n::<4>().collect::<Vec<_>>(),
+            eq(&values
+                .windows(4)
+                .map(|w| [w[0], w[1], w[2], w[3]])
+                .collect::<Vec<_>>())
+        )
+    }
+
+    #[gtest]
+    fn test_unordered_pairs() -> googletest::Result<()> {
+        verify_that!(
+            unordered_pairs(&['a', 'b', 'c']).collect::<Vec<_>>(),
+            eq(&vec![(&'a', &'b'), (&'a', &'c'), (&'b', &'c')])
+        )?;
+        verify_that!(unordered_pairs(&[1]).count(), eq(0))
+    }
+
+    #[gtest]
+    fn test_combinations() -> googletest::Result<()> {
+        verify_that!(
+            combinations(&[1, 2, 3, 4], 2)
+                .map(|c| c.into_iter().copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            eq(&vec![
+                vec![1, 2],
+                vec![1, 3],
+                vec![1, 4],
+                vec![2, 3],
+                vec![2, 4],
+                vec![3, 4]
+            ])
+        )?;
+        verify_that!(combinations(&[0; 10], 3).count(), eq(120))?;
+        verify_that!(combinations(&[1, 2], 0).count(), eq(1))?;
+        verify_that!(combinations(&[1, 2], 3).count(), eq(0))
+    }
+
+    #[gtest]
+    fn test_permutations() -> googletest::Result<()> {
+        verify_that!(
+            permutations(&['a', 'b', 'c'])
+                .map(|p| p.into_iter().collect::<String>())
+                .collect::<Vec<_>>(),
+            eq(&vec!["abc", "acb", "bac", "bca", "cab", "cba"])
+        )?;
+        verify_that!(permutations(&[1, 1, 2, 3, 5]).count(), eq(120))?;
+        verify_that!(permutations::<u8>(&[]).count(), eq(1))
+    }
+
+    #[gtest]
+    fn test_parse_points() -> googletest::Result<()> {
+        verify_that!(
+            parse_points::<u8, 1>("10,27\n\n1,2\n"),
+            ok(eq(&vec![[(10, 27)], [(1, 2)]]))
+        )?;
+        verify_that!(
+            parse_points::<u8, 1>("1,2\n300,4"),
+            err(eq("Line 2: expected a point like x,y, found \"300,4\""))
+        )?;
+        verify_that!(
+            parse_points::<i64, 2>("p=0,4 v=3,-3\np=6,3"),
+            err(eq("Line 2: expected 2 points, found \"p=6,3\""))
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn dir_to_stays_in_bounds((tiles, index) in tile(), dir in direction()) {
+            if let Some(next) = tiles.dir_to(index, dir) {
+                prop_assert!(tiles.contains(next));
+            }
+        }
+
+        #[test]
+        fn dir_to_and_back((tiles, index) in tile(), dir in direction()) {
+            if let Some(next) = tiles.dir_to(index, dir) {
+                prop_assert_eq!(tiles.dir_to(next, dir.opposite()), Some(index));
+            }
+        }
+
+        #[test]
+        fn dir_to_stops_only_at_edges((tiles, index) in tile(), dir in direction()) {
+            let (row, col) = (index / tiles.width, index % tiles.width);
+            let at_edge = match dir {
+                Direction::Left => col == 0,
+                Direction::Right => col == tiles.width - 1,
+                Direction::Up => row == 0,
+                Direction::Down => row == tiles.height - 1,
+            };
+            prop_assert_eq!(tiles.dir_to(index, dir).is_none(), at_edge);
+        }
+
+        #[test]
+        fn neighbors4_agree_with_dir_to((tiles, index) in tile()) {
+            let neighbors: Vec<_> = tiles.neighbors4(index).collect();
+            for dir in DIRECTIONS {
+                let expected = tiles.dir_to(index, dir).map(|next| (next, dir));
+                prop_assert_eq!(neighbors.iter().find(|(_, d)| *d == dir).copied(), expected);
+            }
+        }
+
+        #[test]
+        fn within_manhattan_is_every_close_tile((tiles, index) in tile(), k in 0..6usize) {
+            let found: Vec<_> = tiles.within_manhattan(index, k).collect();
+            let (row, col) = tiles.to_rc(index);
+            let expected: Vec<_> = (0..tiles.width * tiles.height)
+                .map(|other| {
+                    let (r, c) = tiles.to_rc(other);
+                    (other, r.abs_diff(row) + c.abs_diff(col))
+                })
+                .filter(|&(_, distance)| distance <= k)
+                .collect();
+            prop_assert_eq!(found, expected);
+        }
+
+        #[test]
+        fn checked_step_agrees_with_dir_to((tiles, index) in tile(), dir in direction()) {
+            let stepped = Vec2::from_index(&tiles, index).checked_step(dir, &tiles);
+            prop_assert_eq!(
+                stepped,
+                tiles.dir_to(index, dir).map(|next| Vec2::from_index(&tiles, next))
+            );
+        }
+
+        #[test]
+        fn vec2_index_round_trips((tiles, index) in tile()) {
+            prop_assert_eq!(Vec2::from_index(&tiles, index).to_index(&tiles), Some(index));
+        }
+
+        #[test]
+        fn rc_round_trips((tiles, index) in tile()) {
+            let (row, col) = tiles.to_rc(index);
+            prop_assert!(row < tiles.height && col < tiles.width);
+            prop_assert_eq!(tiles.from_rc(row, col), index);
+        }
+
+        #[test]
+        fn border_is_where_dir_to_stops((tiles, index) in tile(), dir in direction()) {
+            let on_border = tiles.border().any(|tile| tile == (index, dir));
+            prop_assert_eq!(on_border, tiles.dir_to(index, dir).is_none());
+        }
+
+        #[test]
+        fn delta_agrees_with_dir_to((tiles, index) in tile(), dir in direction()) {
+            let (row, col) = tiles.to_rc(index);
+            let (delta_row, delta_col) = dir.delta();
+            let expected = row
+                .checked_add_signed(delta_row)
+                .zip(col.checked_add_signed(delta_col))
+                .filter(|&(r, c)| r < tiles.height && c < tiles.width)
+                .map(|(r, c)| tiles.from_rc(r, c));
+            prop_assert_eq!(tiles.dir_to(index, dir), expected);
+            prop_assert_eq!(Direction8::from(dir).delta(), dir.delta());
+        }
+
+        #[test]
+        fn dir8_agrees_with_dir_to((tiles, index) in tile(), dir in direction()) {
+            prop_assert_eq!(tiles.dir8_to(index, dir.into()), tiles.dir_to(index, dir));
+        }
+
+        #[test]
+        fn turns8_go_all_the_way_around(n in 0..8usize) {
+            let dir = DIRECTIONS8[n];
+            prop_assert_eq!(dir.clock().counterclock(), dir);
+            prop_assert_eq!((0..8).fold(dir, |d, _| d.clock()), dir);
+            prop_assert_ne!(dir.clock().is_diagonal(), dir.is_diagonal());
+            prop_assert_eq!(dir.opposite().delta(), (-dir.delta().0, -dir.delta().1));
+            let (row, col) = dir.delta();
+            let (turned_row, turned_col) = dir.clock().clock().delta();
+            prop_assert_eq!((turned_row, turned_col), (col, -row));
+        }
+
+        #[test]
+        fn turns_undo_each_other(dir in direction()) {
+            prop_assert_eq!(dir.clock().counterclock(), dir);
+            prop_assert_eq!(dir.clock().clock().clock().clock(), dir);
+            prop_assert_eq!(dir.clock().clock(), dir.opposite());
+            prop_assert_ne!(dir.opposite(), dir);
+            prop_assert_eq!(dir.opposite().opposite(), dir);
+            prop_assert_eq!(dir.opposite().is_horizontal(), dir.is_horizontal());
+        }
+    }
 }
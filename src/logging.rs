@@ -0,0 +1,48 @@
+//! A minimal `log` backend.  Messages go to stderr, so answers on stdout
+//! stay easy to pipe around.
+use log::{LevelFilter, Log, Metadata, Record};
+
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Warnings only by default; `-v` adds debug output and `-vv` trace.
+pub fn level_filter(verbosity: u8) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+pub fn init(verbosity: u8) {
+    // Only fails if a logger is already set, which is fine.
+    let _ = log::set_logger(&StderrLogger);
+    log::set_max_level(level_filter(verbosity));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_level_filter() -> Result<()> {
+        verify_that!(level_filter(0), eq(LevelFilter::Warn))?;
+        verify_that!(level_filter(1), eq(LevelFilter::Debug))?;
+        verify_that!(level_filter(2), eq(LevelFilter::Trace))?;
+        verify_that!(level_filter(5), eq(LevelFilter::Trace))
+    }
+}
@@ -0,0 +1,384 @@
+//! Exact linear algebra for small systems, like "how many presses of each
+//! button reach the prize": matrices of rationals, so elimination never
+//! loses a remainder to rounding.
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Index, Mul, Neg, Sub};
+
+/// A fraction in lowest terms, with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: i128,
+    denom: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { numer: 0, denom: 1 };
+    pub const ONE: Rational = Rational { numer: 1, denom: 1 };
+
+    /// Panics if `denom` is zero.
+    pub fn new(numer: i128, denom: i128) -> Self {
+        assert!(denom != 0, "Zero denominator");
+        let g = gcd(numer, denom) * denom.signum();
+        Self {
+            numer: numer / g,
+            denom: denom / g,
+        }
+    }
+
+    pub fn numer(self) -> i128 {
+        self.numer
+    }
+
+    pub fn denom(self) -> i128 {
+        self.denom
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.numer == 0
+    }
+
+    /// The value, if it's a whole number.
+    pub fn to_integer(self) -> Option<i128> {
+        (self.denom == 1).then_some(self.numer)
+    }
+
+    /// Panics on zero.
+    pub fn recip(self) -> Self {
+        Self::new(self.denom, self.numer)
+    }
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Self {
+            numer: n.into(),
+            denom: 1,
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+    fn add(self, other: Rational) -> Rational {
+        Rational::new(
+            self.numer * other.denom + other.numer * self.denom,
+            self.denom * other.denom,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+    fn sub(self, other: Rational) -> Rational {
+        self + -other
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+    fn neg(self) -> Rational {
+        Rational {
+            numer: -self.numer,
+            ..self
+        }
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+    fn mul(self, other: Rational) -> Rational {
+        Rational::new(self.numer * other.numer, self.denom * other.denom)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+    fn div(self, other: Rational) -> Rational {
+        assert!(!other.is_zero(), "Division by zero");
+        Rational::new(self.numer * other.denom, self.denom * other.numer)
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numer * other.denom).cmp(&(other.numer * self.denom))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.to_integer() {
+            Some(n) => write!(f, "{}", n),
+            None => write!(f, "{}/{}", self.numer, self.denom),
+        }
+    }
+}
+
+/// A matrix of rationals, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<Rational>,
+}
+
+impl Matrix {
+    /// Panics unless every row is as long as the first.
+    pub fn from_rows<R: AsRef<[i64]>>(rows: &[R]) -> Self {
+        let cols = rows.first().map_or(0, |row| row.as_ref().len());
+        let mut data = Vec::with_capacity(rows.len() * cols);
+        for row in rows {
+            assert_eq!(row.as_ref().len(), cols, "Ragged matrix");
+            data.extend(row.as_ref().iter().map(|&n| Rational::from(n)));
+        }
+        Self {
+            rows: rows.len(),
+            cols,
+            data,
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut data = vec![Rational::ZERO; n * n];
+        for i in 0..n {
+            data[i * n + i] = Rational::ONE;
+        }
+        Self {
+            rows: n,
+            cols: n,
+            data,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for col in 0..self.cols {
+            self.data.swap(a * self.cols + col, b * self.cols + col);
+        }
+    }
+
+    /// Gauss-Jordan elimination on the first `self.rows` columns, carrying
+    /// the rest along, as for an augmented matrix.  Returns the
+    /// determinant of that square part; if it's zero, elimination stopped
+    /// part way.
+    fn eliminate(&mut self) -> Rational {
+        let n = self.rows;
+        let cols = self.cols;
+        let mut det = Rational::ONE;
+        for col in 0..n {
+            let Some(pivot_row) = (col..n).find(|&row| !self.data[row * cols + col].is_zero())
+            else {
+                return Rational::ZERO;
+            };
+            if pivot_row != col {
+                self.swap_rows(pivot_row, col);
+                det = -det;
+            }
+            let pivot = self.data[col * cols + col];
+            det = det * pivot;
+            for c in 0..cols {
+                self.data[col * cols + c] = self.data[col * cols + c] / pivot;
+            }
+            for row in (0..n).filter(|&row| row != col) {
+                let factor = self.data[row * cols + col];
+                if factor.is_zero() {
+                    continue;
+                }
+                for c in 0..cols {
+                    self.data[row * cols + c] =
+                        self.data[row * cols + c] - factor * self.data[col * cols + c];
+                }
+            }
+        }
+        det
+    }
+
+    /// The rows of `self` followed by the columns of `other`.
+    fn augment(&self, other: &Matrix) -> Matrix {
+        assert_eq!(self.rows, other.rows, "Row counts differ");
+        let cols = self.cols + other.cols;
+        let mut data = Vec::with_capacity(self.rows * cols);
+        for row in 0..self.rows {
+            data.extend(&self.data[row * self.cols..(row + 1) * self.cols]);
+            data.extend(&other.data[row * other.cols..(row + 1) * other.cols]);
+        }
+        Matrix {
+            rows: self.rows,
+            cols,
+            data,
+        }
+    }
+
+    /// Panics unless the matrix is square.
+    pub fn determinant(&self) -> Rational {
+        assert_eq!(self.rows, self.cols, "Not a square matrix");
+        self.clone().eliminate()
+    }
+
+    /// The inverse, unless the matrix is singular.  Panics unless it's
+    /// square.
+    pub fn inverse(&self) -> Option<Matrix> {
+        assert_eq!(self.rows, self.cols, "Not a square matrix");
+        let n = self.rows;
+        let mut augmented = self.augment(&Matrix::identity(n));
+        if augmented.eliminate().is_zero() {
+            return None;
+        }
+        let data = (0..n)
+            .flat_map(|row| augmented.data[row * 2 * n + n..(row + 1) * 2 * n].to_vec())
+            .collect();
+        Some(Matrix {
+            rows: n,
+            cols: n,
+            data,
+        })
+    }
+
+    /// The `x` with `self * x = rhs`, if there's exactly one.  Panics
+    /// unless the matrix is square and `rhs` is as long as it is tall.
+    pub fn solve(&self, rhs: &[i64]) -> Option<Vec<Rational>> {
+        assert_eq!(self.rows, self.cols, "Not a square matrix");
+        assert_eq!(rhs.len(), self.rows, "Wrong length right-hand side");
+        let column: Vec<[i64; 1]> = rhs.iter().map(|&n| [n]).collect();
+        let mut augmented = self.augment(&Matrix::from_rows(&column));
+        if augmented.eliminate().is_zero() {
+            return None;
+        }
+        Some(
+            (0..self.rows)
+                .map(|row| augmented[(row, self.cols)])
+                .collect(),
+        )
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = Rational;
+    fn index(&self, (row, col): (usize, usize)) -> &Rational {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({}, {}) is off the {}x{} matrix",
+            row,
+            col,
+            self.rows,
+            self.cols
+        );
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl Mul for &Matrix {
+    type Output = Matrix;
+    fn mul(self, other: &Matrix) -> Matrix {
+        assert_eq!(self.cols, other.rows, "Mismatched shapes");
+        let mut data = Vec::with_capacity(self.rows * other.cols);
+        for row in 0..self.rows {
+            for col in 0..other.cols {
+                data.push(
+                    (0..self.cols)
+                        .map(|k| self[(row, k)] * other[(k, col)])
+                        .fold(Rational::ZERO, |sum, term| sum + term),
+                );
+            }
+        }
+        Matrix {
+            rows: self.rows,
+            cols: other.cols,
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_rational() -> Result<()> {
+        verify_that!(Rational::new(6, -4), eq(Rational::new(-3, 2)))?;
+        verify_that!(Rational::new(-3, 2).denom(), eq(2))?;
+        verify_that!(
+            Rational::new(1, 2) + Rational::new(1, 3),
+            eq(Rational::new(5, 6))
+        )?;
+        verify_that!(
+            Rational::new(1, 2) / Rational::new(-1, 4),
+            eq(Rational::from(-2))
+        )?;
+        verify_that!(Rational::new(4, 2).to_integer(), some(eq(2)))?;
+        verify_that!(Rational::new(1, 2).to_integer(), none())?;
+        verify_that!(Rational::new(1, 3) < Rational::new(1, 2), is_true())?;
+        verify_that!(Rational::new(-7, 3).to_string(), eq("-7/3"))
+    }
+
+    #[gtest]
+    fn test_determinant() -> Result<()> {
+        verify_that!(
+            Matrix::from_rows(&[[94, 22], [34, 67]]).determinant(),
+            eq(Rational::from(5550))
+        )?;
+        // Needs a row swap.
+        verify_that!(
+            Matrix::from_rows(&[[0, 1, 2], [1, 0, 3], [4, -3, 8]]).determinant(),
+            eq(Rational::from(-2))
+        )?;
+        verify_that!(
+            Matrix::from_rows(&[[1, 2], [2, 4]]).determinant(),
+            eq(Rational::ZERO)
+        )
+    }
+
+    #[gtest]
+    fn test_inverse() -> Result<()> {
+        let m = Matrix::from_rows(&[[2, 1, 1], [1, 3, 2], [1, 0, 0]]);
+        let inverse = m.inverse().ok_or("singular").into_test_result()?;
+        verify_that!(&m * &inverse, eq(&Matrix::identity(3)))?;
+        verify_that!(&inverse * &m, eq(&Matrix::identity(3)))?;
+        verify_that!(Matrix::from_rows(&[[1, 2], [2, 4]]).inverse(), none())
+    }
+
+    #[gtest]
+    fn test_solve() -> Result<()> {
+        // The first claw machine from 2024 day 13: 80 presses of A, 40 of B.
+        verify_that!(
+            Matrix::from_rows(&[[94, 22], [34, 67]]).solve(&[8400, 5400]),
+            some(eq(&vec![Rational::from(80), Rational::from(40)]))
+        )?;
+        verify_that!(
+            Matrix::from_rows(&[[1, 1, 1], [0, 2, 5], [2, 5, -1]]).solve(&[6, -4, 27]),
+            some(eq(&vec![
+                Rational::from(5),
+                Rational::from(3),
+                Rational::from(-2)
+            ]))
+        )?;
+        verify_that!(
+            Matrix::from_rows(&[[2, 0], [0, 4]]).solve(&[1, 1]),
+            some(eq(&vec![Rational::new(1, 2), Rational::new(1, 4)]))
+        )?;
+        verify_that!(Matrix::from_rows(&[[1, 2], [2, 4]]).solve(&[1, 2]), none())
+    }
+}
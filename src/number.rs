@@ -0,0 +1,117 @@
+//! Integer arithmetic that keeps coming up: common divisors and
+//! multiples, modular inverses, and the Chinese remainder theorem for
+//! lining up things that repeat with different periods.
+
+/// The greatest common divisor, never negative.  `gcd(0, 0)` is 0.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, never negative.  Zero if either is.
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// The extended Euclidean algorithm: `(g, x, y)` with `a * x + b * y = g`,
+/// where `g` is `gcd(a, b)`.
+pub fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..m` with `a * x = 1 (mod m)`, if `a` and `m` are coprime.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = egcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// The Chinese remainder theorem: given `(residue, modulus)` pairs, the
+/// `(r, m)` such that `x = r (mod m)` exactly when `x` agrees with every
+/// pair, `m` being the lcm of the moduli.  Moduli needn't be coprime; None
+/// if the pairs contradict each other.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let mut result = (0, 1);
+    for (residue, modulus) in congruences {
+        let (r, m) = result;
+        // Solve r + m * k = residue (mod modulus) for k.
+        let (g, inverse, _) = egcd(m, modulus);
+        let diff = residue - r;
+        if diff % g != 0 {
+            return None;
+        }
+        let step = modulus / g;
+        let k = (diff / g) as i128 * inverse as i128 % step as i128;
+        let combined = m / g * modulus;
+        let r = (r as i128 + m as i128 * k).rem_euclid(combined as i128);
+        result = (r as i64, combined);
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_gcd_lcm() -> Result<()> {
+        verify_that!(gcd(12, 18), eq(6))?;
+        verify_that!(gcd(-12, 18), eq(6))?;
+        verify_that!(gcd(0, 5), eq(5))?;
+        verify_that!(gcd(0, 0), eq(0))?;
+        verify_that!(lcm(4, 6), eq(12))?;
+        verify_that!(lcm(101, 103), eq(10403))?;
+        verify_that!(lcm(0, 3), eq(0))
+    }
+
+    #[gtest]
+    fn test_egcd() -> Result<()> {
+        for (a, b) in [(240, 46), (46, 240), (-7, 3), (5, 0), (0, 5), (94, 22)] {
+            let (g, x, y) = egcd(a, b);
+            verify_that!(g, eq(gcd(a, b)))?;
+            verify_that!(a * x + b * y, eq(g))?;
+        }
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_mod_inverse() -> Result<()> {
+        verify_that!(mod_inverse(3, 11), some(eq(4)))?;
+        verify_that!(mod_inverse(-3, 11), some(eq(7)))?;
+        verify_that!(mod_inverse(6, 9), none())
+    }
+
+    #[gtest]
+    fn test_crt() -> Result<()> {
+        verify_that!(crt([(2, 3), (3, 5), (2, 7)]), some(eq((23, 105))))?;
+        // Moduli sharing a factor, agreeing and not.
+        verify_that!(crt([(3, 4), (5, 6)]), some(eq((11, 12))))?;
+        verify_that!(crt([(3, 4), (4, 6)]), none())?;
+        verify_that!(crt([]), some(eq((0, 1))))?;
+        // Large moduli, where the intermediate products overflow i64.
+        let (r, m) = crt([(12, 999_999_937), (34, 999_999_929)])
+            .ok_or("no solution")
+            .into_test_result()?;
+        verify_that!(m, eq(999_999_937 * 999_999_929))?;
+        verify_that!((r % 999_999_937, r % 999_999_929), eq((12, 34)))
+    }
+}
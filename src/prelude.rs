@@ -0,0 +1,20 @@
+//! The names most days need, in one import:
+//!
+//! ```
+//! use advent_2024::prelude::*;
+//!
+//! let grid = Grid::parse_chars("..#\n...", |ch| ch == '#')?;
+//! let distances = bfs(0, |&index| grid.neighbors(index).filter(|&n| !grid[n]).collect::<Vec<_>>());
+//! assert_eq!(distances.distance(&5), Some(3));
+//! # Ok::<(), String>(())
+//! ```
+//!
+//! The more specialized modules, like `graph` or `number`, are imported by
+//! path as needed.
+pub use crate::search::{bfs, dijkstra, small_weight_bfs, DistanceMap};
+pub use crate::{
+    combinations, cycle_len, exponential_search, find_cycle, parse_points, partition_point,
+    permutations, split_sections, unordered_pairs, AdventError, Agent, BitGrid, Direction,
+    Direction8, DirectionSet, FromInput, FxHashMap, FxHashSet, Grid, Memo, Pairwise, Render,
+    Simulate, Solver, SparseGrid, TileIndex, Vec2,
+};
@@ -0,0 +1,57 @@
+//! Flamegraphs of single solver runs, for `advent --profile`.  Sampling
+//! needs the `profile` feature:
+//!
+//! ```text
+//! cargo run --release --features profile --bin advent -- --day 16 --profile
+//! ```
+use crate::AdventError;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+pub const PROFILE_DIR: &str = "profiles";
+
+/// Where the flamegraph for `day`'s `part` goes under `dir`.
+pub fn flamegraph_path(dir: &Path, day: u8, part: u8) -> PathBuf {
+    dir.join(format!("day{:02}-part{}.svg", day, part))
+}
+
+/// Runs `run` under the sampling profiler, writing a flamegraph to `path`.
+#[cfg(feature = "profile")]
+pub fn profiled(
+    path: &Path,
+    run: impl FnOnce() -> Result<String, AdventError>,
+) -> Result<String, Box<dyn Error>> {
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(1000)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()?;
+    let answer = run();
+    let report = guard.report().build()?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    report.flamegraph(std::fs::File::create(path)?)?;
+    Ok(answer?)
+}
+
+#[cfg(not(feature = "profile"))]
+pub fn profiled(
+    _path: &Path,
+    _run: impl FnOnce() -> Result<String, AdventError>,
+) -> Result<String, Box<dyn Error>> {
+    Err("Profiling needs a build with `--features profile`".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_flamegraph_path() -> Result<()> {
+        verify_that!(
+            flamegraph_path(Path::new(PROFILE_DIR), 6, 2),
+            eq(Path::new("profiles/day06-part2.svg"))
+        )
+    }
+}
@@ -0,0 +1,83 @@
+//! A Markdown summary of every day: answers and timings from running on
+//! `inputs/`, and titles and notes from each `Solver`.
+use crate::runner::{total_time, DayRun, Timed};
+use crate::SOLVERS;
+
+pub const REPORT_FILE: &str = "report.md";
+
+/// Makes `s` safe to put in a table cell.
+fn escape(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+fn cells(timed: &Timed) -> String {
+    match &timed.answer {
+        Ok(answer) => format!("{} | {:.1?}", escape(answer), timed.elapsed),
+        Err(e) => format!("*{}* |", escape(e)),
+    }
+}
+
+pub fn markdown_report(runs: &[DayRun]) -> String {
+    let mut report = String::from("# Advent of Code 2024\n\n");
+    report += "| Day | Puzzle | Part 1 | Time | Part 2 | Time | Approach |\n";
+    report += "|----:|--------|--------|-----:|--------|-----:|----------|\n";
+    for (day, solver) in SOLVERS {
+        let results = match runs.iter().find(|run| run.day == *day) {
+            Some(run) => format!("{} | {}", cells(&run.part1), cells(&run.part2)),
+            None => "*no input* | | |".to_string(),
+        };
+        report += &format!(
+            "| {} | {} | {} | {} |\n",
+            day,
+            escape(solver.title()),
+            results,
+            escape(solver.notes())
+        );
+    }
+    report += &format!("\nTotal time: {:.1?}\n", total_time(runs));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use std::time::Duration;
+
+    #[gtest]
+    fn test_markdown_report() -> Result<()> {
+        let runs = [DayRun {
+            day: 17,
+            part1: Timed {
+                answer: Ok("4,6,3".into()),
+                elapsed: Duration::from_micros(20),
+            },
+            part2: Timed {
+                answer: Err("Part 2 is not implemented yet".into()),
+                elapsed: Duration::ZERO,
+            },
+        }];
+        let report = markdown_report(&runs);
+        verify_that!(
+            report,
+            contains_substring(
+                "| 17 | Chronospatial Computer | 4,6,3 | 20.0µs | \
+                 *Part 2 is not implemented yet* | | Interpret"
+            )
+        )?;
+        verify_that!(
+            report,
+            contains_substring("| 1 | Historian Hysteria | *no input* | | | | Sort")
+        )?;
+        verify_that!(report, ends_with("\nTotal time: 20.0µs\n"))
+    }
+
+    #[gtest]
+    fn test_every_solver_has_metadata() -> Result<()> {
+        for (_, solver) in SOLVERS {
+            verify_that!(solver.title(), not(eq("")))?;
+            verify_that!(solver.notes(), not(eq("")))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+//! Runs every implemented day against the puzzle inputs in `inputs/`,
+//! which are expected to be named `day01.txt`, `day02.txt` and so on.
+use crate::{AdventError, SOLVERS};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+pub const INPUT_DIR: &str = "inputs";
+
+/// Where day `day`'s input lives under `dir`.
+pub fn input_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{:02}.txt", day))
+}
+
+#[derive(Debug)]
+pub struct Timed {
+    /// The answer, or the error message if the part failed.
+    pub answer: Result<String, String>,
+    pub elapsed: Duration,
+}
+
+pub fn timed(run: impl FnOnce() -> Result<String, AdventError>) -> Timed {
+    let start = Instant::now();
+    let answer = run().map_err(|e| e.to_string());
+    Timed {
+        answer,
+        elapsed: start.elapsed(),
+    }
+}
+
+#[derive(Debug)]
+pub struct DayRun {
+    pub day: u8,
+    pub part1: Timed,
+    pub part2: Timed,
+}
+
+/// Runs both parts of every day that has an input file in `dir`.
+pub fn run_all(dir: &Path) -> Vec<DayRun> {
+    SOLVERS
+        .iter()
+        .filter_map(|(day, solver)| {
+            let input = std::fs::read_to_string(input_path(dir, *day)).ok()?;
+            Some(DayRun {
+                day: *day,
+                part1: timed(|| solver.part1(&input)),
+                part2: timed(|| solver.part2(&input)),
+            })
+        })
+        .collect()
+}
+
+pub fn total_time(runs: &[DayRun]) -> Duration {
+    runs.iter()
+        .map(|run| run.part1.elapsed + run.part2.elapsed)
+        .sum()
+}
+
+pub fn format_table(runs: &[DayRun]) -> String {
+    let cell = |timed: &Timed| {
+        let answer = match &timed.answer {
+            Ok(answer) => answer.clone(),
+            Err(e) => format!("error: {}", e),
+        };
+        format!("{:<20} {:>10}", answer, format!("{:.1?}", timed.elapsed))
+    };
+    let mut table = format!("{:>3}  {:<31}  {}\n", "Day", "Part 1", "Part 2");
+    for run in runs {
+        table += &format!(
+            "{:>3}  {}  {}\n",
+            run.day,
+            cell(&run.part1),
+            cell(&run.part2)
+        );
+    }
+    table += &format!("Total: {:.1?}\n", total_time(runs));
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use indoc::indoc;
+
+    #[gtest]
+    fn test_input_path() -> Result<()> {
+        verify_that!(
+            input_path(Path::new(INPUT_DIR), 7),
+            eq(Path::new("inputs/day07.txt"))
+        )
+    }
+
+    #[gtest]
+    fn test_format_table() -> Result<()> {
+        let runs = [DayRun {
+            day: 1,
+            part1: Timed {
+                answer: Ok("11".into()),
+                elapsed: Duration::from_millis(2),
+            },
+            part2: Timed {
+                answer: Err("Part 2 is not implemented yet".into()),
+                elapsed: Duration::from_micros(500),
+            },
+        }];
+        verify_that!(
+            format_table(&runs),
+            eq(indoc! {"
+                Day  Part 1                           Part 2
+                  1  11                        2.0ms  error: Part 2 is not implemented yet    500.0µs
+                Total: 2.5ms
+            "})
+        )
+    }
+}
@@ -0,0 +1,270 @@
+//! Sets up a new day: its module in `src/days/`, its binary in `src/bin/`,
+//! an empty example in `fixtures`, and its place in the solver registry.
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+const DAY_TEMPLATE: &str = r#"use crate::prelude::*;
+
+fn parse(input: &str) -> Result<Vec<&str>, AdventError> {
+    Ok(input.lines().collect())
+}
+
+pub struct Day{NN};
+
+impl Solver for Day{NN} {
+    fn title(&self) -> &'static str {
+        "TODO"
+    }
+
+    fn notes(&self) -> &'static str {
+        "TODO"
+    }
+
+    fn part1(&self, input: &str) -> Result<String, AdventError> {
+        let _lines = parse(input)?;
+        Err(AdventError::Unsupported("Part 1 is not implemented yet".into()))
+    }
+
+    fn part2(&self, _input: &str) -> Result<String, AdventError> {
+        Err(AdventError::Unsupported("Part 2 is not implemented yet".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    const DATA: &str = crate::fixtures::DAY{NN}.input;
+
+    #[gtest]
+    fn test_parse() -> Result<()> {
+        verify_that!(parse(DATA), ok(anything()))
+    }
+}
+"#;
+
+const BIN_TEMPLATE: &str = r#"use advent_2024::days::day{NN}::Day{NN};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = advent_2024::cli::Args::from_env(&[])?;
+    advent_2024::logging::init(args.verbosity);
+    if args.sample {
+        return advent_2024::fixtures::print_sample_answers(&Day{NN}, {N}, args.part);
+    }
+    let input = args.read_input()?;
+    advent_2024::print_answers(&Day{NN}, &input, args.part)
+}
+"#;
+
+const FIXTURE_TEMPLATE: &str = r#"// TODO: paste in the example, then add DAY{NN} to SAMPLES.
+pub const DAY{NN}: Sample = Sample {
+    input: "",
+    part1: "",
+    part2: None,
+};
+
+"#;
+
+fn fill(template: &str, day: u8) -> String {
+    template
+        .replace("{NN}", &format!("{:02}", day))
+        .replace("{N}", &day.to_string())
+}
+
+/// Inserts `line` among the lines of `text` that `key` maps to a day,
+/// keeping them in order.  Lines from `start` up to `end`, or the end of
+/// the text, are considered; `key` returns `None` for lines that aren't
+/// entries.
+fn insert_entry(
+    text: &str,
+    start: &str,
+    end: Option<&str>,
+    day: u8,
+    line: &str,
+    key: impl Fn(&str) -> Option<u8>,
+) -> Result<String, String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let first = lines
+        .iter()
+        .position(|l| l.starts_with(start))
+        .ok_or_else(|| format!("Couldn't find {:?}", start))?;
+    let last = match end {
+        Some(end) => {
+            first
+                + lines[first..]
+                    .iter()
+                    .position(|l| l.starts_with(end))
+                    .ok_or_else(|| format!("Couldn't find {:?} after {:?}", end, start))?
+        }
+        None => lines.len(),
+    };
+    let mut at = last;
+    for (i, l) in lines.iter().enumerate().take(last).skip(first) {
+        match key(l) {
+            Some(d) if d == day => return Err(format!("Day {} is already registered", day)),
+            Some(d) if d > day => {
+                at = i;
+                break;
+            }
+            _ => {}
+        }
+    }
+    let mut out: Vec<&str> = lines[..at].to_vec();
+    out.push(line);
+    out.extend(&lines[at..]);
+    Ok(out.join("\n") + "\n")
+}
+
+fn register_module(mod_rs: &str, day: u8) -> Result<String, String> {
+    insert_entry(
+        mod_rs,
+        "pub mod day",
+        None,
+        day,
+        &format!("pub mod day{:02};", day),
+        |l| {
+            l.strip_prefix("pub mod day")?
+                .strip_suffix(';')?
+                .parse()
+                .ok()
+        },
+    )
+}
+
+fn register_solver(lib_rs: &str, day: u8) -> Result<String, String> {
+    insert_entry(
+        lib_rs,
+        "pub const SOLVERS",
+        Some("];"),
+        day,
+        &format!("    ({}, &days::day{:02}::Day{:02}),", day, day, day),
+        |l| l.trim().strip_prefix('(')?.split_once(',')?.0.parse().ok(),
+    )
+}
+
+fn add_fixture(fixtures_rs: &str, day: u8) -> Result<String, String> {
+    let marker = "/// Every day with a usable example";
+    let at = fixtures_rs
+        .find(marker)
+        .ok_or_else(|| format!("Couldn't find {:?}", marker))?;
+    Ok(format!(
+        "{}{}{}",
+        &fixtures_rs[..at],
+        fill(FIXTURE_TEMPLATE, day),
+        &fixtures_rs[at..]
+    ))
+}
+
+/// Creates and registers day `day` in the crate rooted at `root`, returning
+/// the files it wrote.
+pub fn scaffold(root: &Path, day: u8) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let src = root.join("src");
+    let day_rs = src.join(format!("days/day{:02}.rs", day));
+    let bin_rs = src.join(format!("bin/day{:02}.rs", day));
+    for path in [&day_rs, &bin_rs] {
+        if path.exists() {
+            return Err(format!("{} already exists", path.display()).into());
+        }
+    }
+
+    // Work out every edit before writing anything, so a failure leaves the
+    // tree untouched.
+    let mod_rs = src.join("days/mod.rs");
+    let lib_rs = src.join("lib.rs");
+    let fixtures_rs = src.join("fixtures.rs");
+    let writes = [
+        (day_rs, fill(DAY_TEMPLATE, day)),
+        (bin_rs, fill(BIN_TEMPLATE, day)),
+        (
+            mod_rs.clone(),
+            register_module(&std::fs::read_to_string(&mod_rs)?, day)?,
+        ),
+        (
+            lib_rs.clone(),
+            register_solver(&std::fs::read_to_string(&lib_rs)?, day)?,
+        ),
+        (
+            fixtures_rs.clone(),
+            add_fixture(&std::fs::read_to_string(&fixtures_rs)?, day)?,
+        ),
+    ];
+    for (path, contents) in &writes {
+        std::fs::write(path, contents)?;
+    }
+    Ok(writes.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use indoc::indoc;
+
+    #[gtest]
+    fn test_fill() -> Result<()> {
+        verify_that!(
+            fill(BIN_TEMPLATE, 7),
+            contains_substring("print_sample_answers(&Day07, 7, args.part)")
+        )
+    }
+
+    #[gtest]
+    fn test_register_module() -> Result<()> {
+        let mod_rs = indoc! {"
+            //! Days.
+            pub mod day01;
+            pub mod day03;
+        "};
+        verify_that!(
+            register_module(mod_rs, 2),
+            ok(eq(indoc! {"
+                //! Days.
+                pub mod day01;
+                pub mod day02;
+                pub mod day03;
+            "}))
+        )?;
+        verify_that!(
+            register_module(mod_rs, 4),
+            ok(ends_with("pub mod day03;\npub mod day04;\n"))
+        )?;
+        verify_that!(register_module(mod_rs, 3), err(anything()))
+    }
+
+    #[gtest]
+    fn test_register_solver() -> Result<()> {
+        let lib_rs = indoc! {"
+            pub const SOLVERS: &[(u8, &dyn Solver)] = &[
+                (9, &days::day09::Day09),
+                (10, &days::day10::Day10),
+            ];
+
+            pub fn solver() {}
+        "};
+        verify_that!(
+            register_solver(lib_rs, 21),
+            ok(eq(indoc! {"
+                pub const SOLVERS: &[(u8, &dyn Solver)] = &[
+                    (9, &days::day09::Day09),
+                    (10, &days::day10::Day10),
+                    (21, &days::day21::Day21),
+                ];
+
+                pub fn solver() {}
+            "}))
+        )?;
+        verify_that!(register_solver(lib_rs, 10), err(anything()))
+    }
+
+    #[gtest]
+    fn test_add_fixture() -> Result<()> {
+        verify_that!(
+            add_fixture("/// Every day with a usable example, in order.\n", 21),
+            ok(all![
+                starts_with("// TODO: paste in the example, then add DAY21 to SAMPLES.\n"),
+                ends_with("};\n\n/// Every day with a usable example, in order.\n")
+            ])
+        )
+    }
+}
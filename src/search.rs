@@ -0,0 +1,281 @@
+//! Searches over implicit graphs: nodes are any hashable value, like a
+//! grid index or an `Agent`, and edges come from a `neighbors` function.
+use crate::FxHashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// How far each reached node is from the start, in steps, and the node it
+/// was first reached from.
+#[derive(Debug, Clone)]
+pub struct DistanceMap<N> {
+    start: N,
+    reached: FxHashMap<N, (usize, Option<N>)>,
+}
+
+impl<N: Hash + Eq + Clone> DistanceMap<N> {
+    pub fn start(&self) -> &N {
+        &self.start
+    }
+
+    /// The distance to `node`, if it was reached.
+    pub fn distance(&self, node: &N) -> Option<usize> {
+        self.reached.get(node).map(|&(distance, _)| distance)
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.reached.contains_key(node)
+    }
+
+    /// The number of nodes reached, including the start.
+    pub fn len(&self) -> usize {
+        self.reached.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reached.is_empty()
+    }
+
+    /// Every reached node with its distance, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, usize)> {
+        self.reached
+            .iter()
+            .map(|(node, &(distance, _))| (node, distance))
+    }
+
+    /// A shortest path from the start to `goal`, both included, if `goal`
+    /// was reached.
+    pub fn path_to(&self, goal: &N) -> Option<Vec<N>> {
+        let mut path = vec![goal.clone()];
+        let mut current = self.reached.get(goal)?;
+        while let (_, Some(previous)) = current {
+            path.push(previous.clone());
+            current = &self.reached[previous];
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search from `start`, reaching everything it can.
+pub fn bfs<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> DistanceMap<N>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut reached = FxHashMap::default();
+    reached.insert(start.clone(), (0, None));
+    let mut frontier = vec![start.clone()];
+    let mut distance = 0;
+    while !frontier.is_empty() {
+        distance += 1;
+        let mut next_frontier = Vec::new();
+        for node in frontier {
+            for next in neighbors(&node) {
+                if !reached.contains_key(&next) {
+                    reached.insert(next.clone(), (distance, Some(node.clone())));
+                    next_frontier.push(next);
+                }
+            }
+        }
+        frontier = next_frontier;
+    }
+    DistanceMap { start, reached }
+}
+
+/// Shortest distances from `start` when every edge costs a small whole
+/// number, by keeping a deque of buckets, one per distance, instead of a
+/// heap.  With costs of only 0 and 1 this is the usual 0-1 BFS.  Each
+/// pass over the deque costs the largest edge weight, so that should stay
+/// small next to the number of nodes.
+pub fn small_weight_bfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> DistanceMap<N>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    let mut best: FxHashMap<N, (usize, Option<N>)> = FxHashMap::default();
+    let mut reached: FxHashMap<N, (usize, Option<N>)> = FxHashMap::default();
+    best.insert(start.clone(), (0, None));
+    let mut buckets: VecDeque<Vec<N>> = VecDeque::from([vec![start.clone()]]);
+    let mut distance = 0;
+    while let Some(mut bucket) = buckets.pop_front() {
+        while let Some(node) = bucket.pop() {
+            if reached.contains_key(&node) || best[&node].0 != distance {
+                continue;
+            }
+            reached.insert(node.clone(), best[&node].clone());
+            for (next, weight) in successors(&node) {
+                let next_distance = distance + weight;
+                if best.get(&next).is_some_and(|&(d, _)| d <= next_distance) {
+                    continue;
+                }
+                best.insert(next.clone(), (next_distance, Some(node.clone())));
+                if weight == 0 {
+                    bucket.push(next);
+                } else {
+                    if buckets.len() < weight {
+                        buckets.resize_with(weight, Vec::new);
+                    }
+                    buckets[weight - 1].push(next);
+                }
+            }
+        }
+        distance += 1;
+    }
+    DistanceMap { start, reached }
+}
+
+/// Dijkstra's search from `start` for the cheapest way to a node where
+/// `is_goal` holds.  Returns the cost and a cheapest path, both ends
+/// included.  Costs start at `C::default()` and must not be negative.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Hash + Eq + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // Nodes are numbered as they're found, so the heap needn't order them:
+    // each has its best cost so far and the node that cost came through.
+    let mut nodes: Vec<(N, C, Option<usize>)> = vec![(start.clone(), C::default(), None)];
+    let mut ids: FxHashMap<N, usize> = FxHashMap::default();
+    ids.insert(start, 0);
+    let mut heap = BinaryHeap::from([Reverse((C::default(), 0))]);
+    while let Some(Reverse((cost, id))) = heap.pop() {
+        if cost > nodes[id].1 {
+            // Already expanded more cheaply.
+            continue;
+        }
+        if is_goal(&nodes[id].0) {
+            let mut path = vec![nodes[id].0.clone()];
+            let mut current = id;
+            while let Some(previous) = nodes[current].2 {
+                path.push(nodes[previous].0.clone());
+                current = previous;
+            }
+            path.reverse();
+            return Some((cost, path));
+        }
+        let node = nodes[id].0.clone();
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            match ids.get(&next) {
+                Some(&next_id) if next_cost < nodes[next_id].1 => {
+                    nodes[next_id].1 = next_cost;
+                    nodes[next_id].2 = Some(id);
+                    heap.push(Reverse((next_cost, next_id)));
+                }
+                Some(_) => {}
+                None => {
+                    let next_id = nodes.len();
+                    ids.insert(next.clone(), next_id);
+                    nodes.push((next, next_cost, Some(id)));
+                    heap.push(Reverse((next_cost, next_id)));
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn test_bfs_on_grid() -> Result<()> {
+        let grid = Grid::parse_chars("...#\n.#..\n....", |ch| ch == '#').into_test_result()?;
+        let distances = bfs(0, |&index| {
+            grid.neighbors(index)
+                .filter(|&next| !grid[next])
+                .collect::<Vec<_>>()
+        });
+        verify_that!(distances.len(), eq(10))?;
+        verify_that!(distances.distance(&0), some(eq(0)))?;
+        verify_that!(distances.distance(&6), some(eq(3)))?;
+        verify_that!(distances.distance(&3), none())?;
+        verify_that!(distances.path_to(&7), some(eq(&vec![0, 1, 2, 6, 7])))?;
+        verify_that!(distances.path_to(&0), some(eq(&vec![0])))?;
+        verify_that!(distances.path_to(&3), none())
+    }
+
+    #[gtest]
+    fn test_bfs_paths_are_shortest() -> Result<()> {
+        // Counting up by one or doubling, from 1.
+        let distances = bfs(1u32, |&n| [n + 1, n * 2].into_iter().filter(|&m| m <= 40));
+        verify_that!(distances.distance(&40), some(eq(6)))?;
+        let path = distances.path_to(&40).unwrap_or_default();
+        verify_that!(path.len(), eq(7))?;
+        verify_that!(
+            path.windows(2)
+                .all(|w| w[1] == w[0] + 1 || w[1] == w[0] * 2),
+            is_true()
+        )
+    }
+
+    #[gtest]
+    fn test_dijkstra() -> Result<()> {
+        // The direct road is dearer than the way round.
+        let roads = |&town: &char| match town {
+            'a' => vec![('b', 7), ('c', 2)],
+            'c' => vec![('d', 3)],
+            'd' => vec![('b', 1)],
+            _ => vec![],
+        };
+        verify_that!(
+            dijkstra('a', roads, |&town| town == 'b'),
+            some(eq(&(6, vec!['a', 'c', 'd', 'b'])))
+        )?;
+        verify_that!(
+            dijkstra('a', roads, |&town| town == 'a'),
+            some(eq(&(0, vec!['a'])))
+        )?;
+        verify_that!(dijkstra('b', roads, |&town| town == 'a'), none())
+    }
+
+    #[gtest]
+    fn test_dijkstra_agrees_with_bfs_on_unit_costs() -> Result<()> {
+        let neighbors = |&n: &u32| [n + 1, n * 2].into_iter().filter(|&m| m <= 40);
+        let (cost, path) = dijkstra(1u32, |n| neighbors(n).map(|m| (m, 1)), |&n| n == 40)
+            .ok_or("no path")
+            .into_test_result()?;
+        verify_that!(Some(cost), eq(bfs(1, neighbors).distance(&40)))?;
+        verify_that!(path.len(), eq(cost + 1))
+    }
+
+    #[gtest]
+    fn test_zero_one_bfs() -> Result<()> {
+        // Walking along a row is free; changing rows costs one.
+        let grid = Grid::parse_chars("..#.\n#...\n...#", |ch| ch == '#').into_test_result()?;
+        let distances = small_weight_bfs(0, |&index| {
+            grid.tiles
+                .neighbors4(index)
+                .filter(|&(next, _)| !grid[next])
+                .map(|(next, dir)| (next, usize::from(!dir.is_horizontal())))
+                .collect::<Vec<_>>()
+        });
+        verify_that!(distances.distance(&3), some(eq(2)))?;
+        verify_that!(distances.distance(&7), some(eq(1)))?;
+        verify_that!(distances.distance(&8), some(eq(2)))?;
+        verify_that!(distances.path_to(&3), some(eq(&vec![0, 1, 5, 6, 7, 3])))
+    }
+
+    #[gtest]
+    fn test_small_weight_bfs_agrees_with_dijkstra() -> Result<()> {
+        // Counting up by one costs 3, doubling costs 5, and both wrap.
+        let moves = |&n: &u32| [((n + 1) % 50, 3), ((n * 2) % 50, 5)];
+        let distances = small_weight_bfs(1, moves);
+        verify_that!(distances.len(), eq(50))?;
+        for goal in 0..50 {
+            let expected = dijkstra(1, moves, |&n| n == goal).map(|(cost, _)| cost);
+            verify_that!(distances.distance(&goal), eq(expected))?;
+        }
+        Ok(())
+    }
+}
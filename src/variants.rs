@@ -0,0 +1,125 @@
+//! Some parts have been solved more than one way, usually a
+//! straightforward version and a faster one.  Running them all on the same
+//! input keeps the faster ones honest.
+use crate::days::{
+    day02, day03, day05, day06, day07, day09, day10, day11, day12, day13, day16, day18, day19,
+};
+use crate::runner::{timed, Timed};
+use crate::AdventError;
+
+/// One way of solving one part of a puzzle.
+pub struct Variant {
+    pub name: &'static str,
+    pub run: fn(&str) -> Result<String, AdventError>,
+}
+
+/// Every part with more than one variant, as (day, part, variants).  The
+/// first variant is the one the day's `Solver` uses.
+pub const ALTERNATIVES: &[(u8, u8, &[Variant])] = &[
+    (2, 2, day02::PART2_VARIANTS),
+    (3, 1, day03::PART1_VARIANTS),
+    (3, 2, day03::PART2_VARIANTS),
+    (5, 1, day05::PART1_VARIANTS),
+    (5, 2, day05::PART2_VARIANTS),
+    (6, 2, day06::PART2_VARIANTS),
+    (7, 1, day07::PART1_VARIANTS),
+    (7, 2, day07::PART2_VARIANTS),
+    (9, 1, day09::PART1_VARIANTS),
+    (9, 2, day09::PART2_VARIANTS),
+    (10, 2, day10::PART2_VARIANTS),
+    (11, 1, day11::PART1_VARIANTS),
+    (12, 1, day12::PART1_VARIANTS),
+    (12, 2, day12::PART2_VARIANTS),
+    (13, 1, day13::PART1_VARIANTS),
+    (16, 1, day16::PART1_VARIANTS),
+    (18, 2, day18::PART2_VARIANTS),
+    (19, 1, day19::PART1_VARIANTS),
+    (19, 2, day19::PART2_VARIANTS),
+];
+
+pub struct Comparison {
+    pub day: u8,
+    pub part: u8,
+    pub results: Vec<(&'static str, Timed)>,
+}
+
+impl Comparison {
+    /// Whether every variant came up with the same answer.
+    pub fn agrees(&self) -> bool {
+        let mut answers = self.results.iter().map(|(_, timed)| &timed.answer);
+        let first = answers.next();
+        answers.all(|answer| Some(answer) == first)
+    }
+}
+
+pub fn compare(day: u8, part: u8, variants: &[Variant], input: &str) -> Comparison {
+    Comparison {
+        day,
+        part,
+        results: variants
+            .iter()
+            .map(|variant| (variant.name, timed(|| (variant.run)(input))))
+            .collect(),
+    }
+}
+
+pub fn format_comparison(comparison: &Comparison) -> String {
+    let verdict = if comparison.agrees() {
+        "agree"
+    } else {
+        "DISAGREE"
+    };
+    let mut out = format!(
+        "Day {:>2} part {}: {}\n",
+        comparison.day, comparison.part, verdict
+    );
+    for (name, timed) in &comparison.results {
+        let answer = match &timed.answer {
+            Ok(answer) => answer.clone(),
+            Err(e) => format!("error: {}", e),
+        };
+        out += &format!(
+            "  {:<20} {:<20} {:>10}\n",
+            name,
+            answer,
+            format!("{:.1?}", timed.elapsed)
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::sample;
+    use googletest::prelude::*;
+    use std::time::Duration;
+
+    #[gtest]
+    fn test_variants_agree_on_samples() -> Result<()> {
+        for (day, part, variants) in ALTERNATIVES {
+            let Some(sample) = sample(*day) else {
+                continue;
+            };
+            let comparison = compare(*day, *part, variants, sample.input);
+            verify_that!(comparison.agrees(), eq(true))
+                .with_failure_message(|| format_comparison(&comparison))?;
+        }
+        Ok(())
+    }
+
+    #[gtest]
+    fn test_agrees() -> Result<()> {
+        let timed = |answer: &str| Timed {
+            answer: Ok(answer.into()),
+            elapsed: Duration::ZERO,
+        };
+        let comparison = |answers: &[&str]| Comparison {
+            day: 1,
+            part: 1,
+            results: answers.iter().map(|a| ("variant", timed(a))).collect(),
+        };
+        verify_that!(comparison(&["6", "6", "6"]).agrees(), eq(true))?;
+        verify_that!(comparison(&["6", "7"]).agrees(), eq(false))
+    }
+}
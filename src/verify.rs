@@ -0,0 +1,154 @@
+//! Checks solver output against known answers, kept in a TOML file with a
+//! table per day:
+//!
+//! ```toml
+//! [day01]
+//! part1 = 1234
+//! part2 = "5678"
+//! ```
+//!
+//! Answers may be integers or strings.  A missing part is skipped.
+use crate::runner::input_path;
+use crate::solver;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::Path;
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Expected answers, keyed by (day, part).
+pub type Answers = BTreeMap<(u8, u8), String>;
+
+pub fn parse_answers(text: &str) -> Result<Answers, Box<dyn Error>> {
+    let table: toml::Table = text.parse()?;
+    let mut answers = Answers::new();
+    for (day_key, parts) in &table {
+        let day: u8 = day_key
+            .strip_prefix("day")
+            .and_then(|d| d.parse().ok())
+            .ok_or_else(|| format!("Bad day key: {:?}", day_key))?;
+        let parts = parts
+            .as_table()
+            .ok_or_else(|| format!("[{}] must be a table", day_key))?;
+        for (part_key, value) in parts {
+            let part = match part_key.as_str() {
+                "part1" => 1,
+                "part2" => 2,
+                _ => return Err(format!("Bad part key in [{}]: {:?}", day_key, part_key).into()),
+            };
+            let expected = match value {
+                toml::Value::String(s) => s.clone(),
+                toml::Value::Integer(n) => n.to_string(),
+                _ => {
+                    return Err(
+                        format!("{}.{} must be a string or integer", day_key, part_key).into(),
+                    )
+                }
+            };
+            answers.insert((day, part), expected);
+        }
+    }
+    Ok(answers)
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub expected: String,
+    /// The solver's answer, or why there isn't one.
+    pub actual: Result<String, String>,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        self.actual.as_ref() == Ok(&self.expected)
+    }
+}
+
+/// Runs every part that has an expected answer on its input in `dir`.
+pub fn verify(answers: &Answers, dir: &Path) -> Vec<Check> {
+    let mut inputs = BTreeMap::new();
+    answers
+        .iter()
+        .map(|(&(day, part), expected)| {
+            let input = inputs
+                .entry(day)
+                .or_insert_with(|| std::fs::read_to_string(input_path(dir, day)));
+            let actual = match (solver(day), input) {
+                (None, _) => Err(format!("Day {} is not implemented yet", day)),
+                (_, Err(e)) => Err(format!("No input: {}", e)),
+                (Some(solver), Ok(input)) => solver.part(part, input).map_err(|e| e.to_string()),
+            };
+            Check {
+                day,
+                part,
+                expected: expected.clone(),
+                actual,
+            }
+        })
+        .collect()
+}
+
+pub fn format_check(check: &Check) -> String {
+    let verdict = match &check.actual {
+        _ if check.passed() => "ok".to_string(),
+        Ok(actual) => format!("FAIL: expected {}, got {}", check.expected, actual),
+        Err(e) => format!("FAIL: {}", e),
+    };
+    format!("Day {:>2} part {}: {}", check.day, check.part, verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use indoc::indoc;
+
+    #[gtest]
+    fn test_parse_answers() -> Result<()> {
+        let answers = parse_answers(indoc! {r#"
+            [day01]
+            part1 = 11
+            part2 = "31"
+
+            [day17]
+            part1 = "4,6,3,5,6,3,5,2,1,0"
+        "#})
+        .unwrap();
+        verify_that!(
+            answers,
+            eq(&Answers::from([
+                ((1, 1), "11".to_string()),
+                ((1, 2), "31".to_string()),
+                ((17, 1), "4,6,3,5,6,3,5,2,1,0".to_string()),
+            ]))
+        )
+    }
+
+    #[gtest]
+    fn test_parse_answers_rejects_bad_keys() -> Result<()> {
+        verify_that!(parse_answers("[one]\npart1 = 1"), err(anything()))?;
+        verify_that!(parse_answers("[day01]\npart3 = 1"), err(anything()))?;
+        verify_that!(parse_answers("[day01]\npart1 = 1.5"), err(anything()))
+    }
+
+    #[gtest]
+    fn test_format_check() -> Result<()> {
+        let check = |actual: std::result::Result<&str, &str>| Check {
+            day: 1,
+            part: 2,
+            expected: "31".into(),
+            actual: actual.map(String::from).map_err(String::from),
+        };
+        verify_that!(format_check(&check(Ok("31"))), eq("Day  1 part 2: ok"))?;
+        verify_that!(
+            format_check(&check(Ok("30"))),
+            eq("Day  1 part 2: FAIL: expected 31, got 30")
+        )?;
+        verify_that!(
+            format_check(&check(Err("No input"))),
+            eq("Day  1 part 2: FAIL: No input")
+        )
+    }
+}
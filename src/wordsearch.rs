@@ -0,0 +1,234 @@
+//! Finding words and shapes in a grid of letters: words read in a straight
+//! line any of eight ways, and stencils, small blocks of letters with
+//! holes, like day 4's X-MAS cross.
+use crate::{Direction8, Grid, DIRECTIONS8};
+use std::rc::Rc;
+
+pub struct WordSearch {
+    letters: Grid<char>,
+}
+
+impl WordSearch {
+    pub fn new(letters: Grid<char>) -> Self {
+        Self { letters }
+    }
+
+    pub fn parse(input: &str) -> Result<Self, String> {
+        Ok(Self::new(Grid::parse_chars(input, |ch| ch)?))
+    }
+
+    pub fn letters(&self) -> &Grid<char> {
+        &self.letters
+    }
+
+    /// Whether `word` reads from the tile at `index` going `dir`.
+    pub fn reads(&self, word: &str, index: usize, dir: Direction8) -> bool {
+        self.reads_chars(&word.chars().collect::<Vec<_>>(), index, dir)
+    }
+
+    fn reads_chars(&self, word: &[char], index: usize, dir: Direction8) -> bool {
+        let Some(last) = word.len().checked_sub(1) else {
+            return true;
+        };
+        // If the last letter is on the grid, so is every letter before it,
+        // and each step is a fixed jump through the tiles.
+        let tiles = &self.letters.tiles;
+        let (row, col) = tiles.to_rc(index);
+        let (delta_row, delta_col) = dir.delta();
+        let fits = |start: usize, delta: isize, limit: usize| {
+            start
+                .checked_add_signed(delta * last as isize)
+                .is_some_and(|end| end < limit)
+        };
+        if !fits(row, delta_row, tiles.height) || !fits(col, delta_col, tiles.width) {
+            return false;
+        }
+        let step = delta_row * tiles.width as isize + delta_col;
+        word.iter()
+            .enumerate()
+            .all(|(i, &ch)| self.letters[index.wrapping_add_signed(step * i as isize)] == ch)
+    }
+
+    /// Every place `word` reads in a straight line, as the index of its
+    /// first letter and the way it reads from there.  A palindrome turns up
+    /// twice at each place, once each way.
+    pub fn find_word(&self, word: &str) -> impl Iterator<Item = (usize, Direction8)> + '_ {
+        let word: Rc<[char]> = word.chars().collect();
+        let first = word.first().copied();
+        (0..self.letters.len())
+            .filter(move |&index| first.is_none_or(|ch| self.letters[index] == ch))
+            .flat_map(move |index| {
+                let word = Rc::clone(&word);
+                DIRECTIONS8
+                    .into_iter()
+                    .filter(move |&dir| self.reads_chars(&word, index, dir))
+                    .map(move |dir| (index, dir))
+            })
+    }
+
+    pub fn count_word(&self, word: &str) -> usize {
+        self.find_word(word).count()
+    }
+
+    /// The index of the top-left tile of every block that `stencil`
+    /// matches, row by row.
+    pub fn find_stencil(&self, stencil: &Stencil) -> impl Iterator<Item = usize> + '_ {
+        let tiles = &self.letters.tiles;
+        let pattern = &stencil.cells.tiles;
+        let offsets = self.offsets(stencil);
+        let rows = (tiles.height + 1).saturating_sub(pattern.height);
+        let cols = (tiles.width + 1).saturating_sub(pattern.width);
+        (0..rows)
+            .flat_map(move |row| (0..cols).map(move |col| tiles.from_rc(row, col)))
+            .filter(move |&index| {
+                offsets
+                    .iter()
+                    .all(|&(offset, ch)| self.letters[index + offset] == ch)
+            })
+    }
+
+    /// Each letter of `stencil`, with how far past the top-left tile of a
+    /// block it falls.
+    fn offsets(&self, stencil: &Stencil) -> Vec<(usize, char)> {
+        let width = self.letters.tiles.width;
+        stencil
+            .cells
+            .iter_with_index()
+            .filter_map(|(index, cell)| {
+                let (row, col) = stencil.cells.tiles.to_rc(index);
+                Some((row * width + col, (*cell)?))
+            })
+            .collect()
+    }
+
+    /// How many blocks match any of `stencils`; a block matching more than
+    /// one counts more than once.
+    pub fn count_stencils(&self, stencils: &[Stencil]) -> usize {
+        stencils
+            .iter()
+            .map(|stencil| self.find_stencil(stencil).count())
+            .sum()
+    }
+
+    /// The tiles of the match `find_word` reports as `(index, dir)`.
+    pub fn word_tiles(&self, word: &str, index: usize, dir: Direction8) -> Vec<usize> {
+        let (delta_row, delta_col) = dir.delta();
+        let step = delta_row * self.letters.tiles.width as isize + delta_col;
+        (0..word.chars().count())
+            .map(|i| index.wrapping_add_signed(step * i as isize))
+            .collect()
+    }
+
+    /// The lettered tiles of the match `find_stencil` reports at `index`.
+    pub fn stencil_tiles(&self, stencil: &Stencil, index: usize) -> Vec<usize> {
+        self.offsets(stencil)
+            .into_iter()
+            .map(|(offset, _)| index + offset)
+            .collect()
+    }
+
+    /// The grid with every letter but those at `shown` dimmed to `.`, as
+    /// the puzzle draws where its matches are.
+    pub fn overlay(&self, shown: impl IntoIterator<Item = usize>) -> String {
+        let mut overlay = self.letters.map(|_| '.');
+        for index in shown {
+            overlay[index] = self.letters[index];
+        }
+        overlay.render_with(|&ch| ch)
+    }
+}
+
+/// A block of letters to look for, where `None` matches any letter.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Stencil {
+    cells: Grid<Option<char>>,
+}
+
+impl Stencil {
+    /// Reads a stencil from rows of letters, with `.` for a hole.
+    pub fn parse(pattern: &str) -> Result<Self, String> {
+        Ok(Self {
+            cells: Grid::parse_chars(pattern, |ch| (ch != '.').then_some(ch))?,
+        })
+    }
+
+    /// The stencil turned a quarter turn clockwise.
+    pub fn rotate(&self) -> Self {
+        let (width, height) = (self.cells.tiles.height, self.cells.tiles.width);
+        Self {
+            cells: Grid::from_fn(width, height, |index| {
+                let (row, col) = (index / width, index % width);
+                self.cells[(width - 1 - col, row)]
+            }),
+        }
+    }
+
+    /// The stencil in each of its four quarter turns, leaving out turns
+    /// that look the same as one already given.
+    pub fn rotations(&self) -> Vec<Self> {
+        let mut rotations: Vec<Self> = vec![self.clone()];
+        for _ in 0..3 {
+            let next = rotations[rotations.len() - 1].rotate();
+            if !rotations.contains(&next) {
+                rotations.push(next);
+            }
+        }
+        rotations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    const DATA: &str = crate::fixtures::DAY04.input;
+
+    #[gtest]
+    fn test_count_word() -> Result<()> {
+        let search = WordSearch::parse(DATA).into_test_result()?;
+        verify_that!(search.count_word("XMAS"), eq(18))?;
+        verify_that!(search.count_word(""), eq(search.letters().len() * 8))?;
+        let search = WordSearch::parse("ABA").into_test_result()?;
+        verify_that!(search.count_word("ABA"), eq(2))?;
+        verify_that!(search.count_word("ABAB"), eq(0))
+    }
+
+    #[gtest]
+    fn test_find_word() -> Result<()> {
+        let search = WordSearch::parse("..X\n.M.\nA..").into_test_result()?;
+        verify_that!(
+            search.find_word("XMA").collect::<Vec<_>>(),
+            eq(&vec![(2, Direction8::DownLeft)])
+        )
+    }
+
+    #[gtest]
+    fn test_rotate() -> Result<()> {
+        let stencil = Stencil::parse("AB.\nCDE").into_test_result()?;
+        verify_that!(
+            stencil.rotate(),
+            eq(&Stencil::parse("CA\nDB\nE.").into_test_result()?)
+        )?;
+        verify_that!(stencil.rotate().rotate().rotate().rotate(), eq(&stencil))?;
+        verify_that!(stencil.rotations().len(), eq(4))?;
+        verify_that!(
+            Stencil::parse("M.M\n.A.\nM.M")
+                .into_test_result()?
+                .rotations()
+                .len(),
+            eq(1)
+        )
+    }
+
+    #[gtest]
+    fn test_count_stencils() -> Result<()> {
+        let search = WordSearch::parse(DATA).into_test_result()?;
+        let cross = Stencil::parse("M.S\n.A.\nM.S").into_test_result()?;
+        verify_that!(search.count_stencils(&cross.rotations()), eq(9))?;
+        verify_that!(
+            search.find_stencil(&cross).collect::<Vec<_>>(),
+            eq(&vec![1, 21])
+        )
+    }
+}